@@ -1,3 +1,15 @@
+use std::{thread, time::Duration};
+
+use crate::clock::Clock;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Lifecycle {
+    FocusLost,
+    FocusGained,
+    Suspended,
+    Resumed,
+}
+
 pub trait App {
     type EventError;
 
@@ -8,29 +20,107 @@ pub trait App {
     fn update(&mut self, delta: f64);
 
     fn draw(&mut self, interp: f64, total_time: u64);
+
+    //Lifecycle events seen while processing events, drained once per loop
+    fn lifecycle_events(&mut self) -> Vec<Lifecycle> {
+        Vec::new()
+    }
+
+    fn start(&mut self) {}
+
+    fn shutdown(&mut self) {}
+
+    fn focus_lost(&mut self) {}
+
+    fn focus_gained(&mut self) {}
+
+    fn suspend(&mut self) {}
+
+    fn resume(&mut self) {}
+
+    //Called every loop while suspended instead of updating and drawing.
+    //Sleeps for a step by default so the loop doesn't spin while in the background.
+    fn wait_while_suspended(&mut self, step: u64) {
+        thread::sleep(Duration::from_millis(step));
+    }
 }
 
 pub fn gameloop<T: App>(app: &mut T, fps: i32) -> Result<(), T::EventError> {
+    run(app, fps, |app| app.ticks())
+}
+
+//Same as gameloop, but time is read from the clock instead of App::ticks
+pub fn gameloop_with_clock<T: App, C: Clock>(
+    app: &mut T,
+    clock: &C,
+    fps: i32,
+) -> Result<(), T::EventError> {
+    run(app, fps, |_| clock.ticks())
+}
+
+fn run<T: App, F: Fn(&T) -> u64>(app: &mut T, fps: i32, ticks: F) -> Result<(), T::EventError> {
+    app.start();
+    let result = mainloop(app, fps, ticks);
+    app.shutdown();
+
+    result
+}
+
+fn mainloop<T: App, F: Fn(&T) -> u64>(
+    app: &mut T,
+    fps: i32,
+    ticks: F,
+) -> Result<(), T::EventError> {
     //We do not do 1/fps for delta because of the difference between 16 and 16.6...
     //Probably not a major difference
     let step = 1000 / fps as u64;
     let delta = step as f64 / 1000.0;
 
-    let mut previous = app.ticks();
+    let mut previous = ticks(app);
     let mut lag = 0;
+    let mut suspended = false;
 
     'mainloop: loop {
-        let total = app.ticks();
+        let total = ticks(app);
         let current = total;
-        let elapsed = current - previous;
+        //A clock set back, like a replay rewinding, counts as no time passing
+        let elapsed = current.saturating_sub(previous);
 
         previous = current;
-        lag += elapsed;
+        //Time spent suspended is dropped rather than caught up on
+        if !suspended {
+            lag += elapsed;
+        }
 
         if !app.processed_events()? {
             break 'mainloop;
         }
 
+        for event in app.lifecycle_events() {
+            match event {
+                Lifecycle::FocusLost => app.focus_lost(),
+                Lifecycle::FocusGained => app.focus_gained(),
+                Lifecycle::Suspended => {
+                    if !suspended {
+                        suspended = true;
+                        app.suspend();
+                    }
+                }
+                Lifecycle::Resumed => {
+                    if suspended {
+                        suspended = false;
+                        lag = 0;
+                        app.resume();
+                    }
+                }
+            }
+        }
+
+        if suspended {
+            app.wait_while_suspended(step);
+            continue 'mainloop;
+        }
+
         while lag >= step {
             app.update(delta);
 
@@ -0,0 +1,152 @@
+use std::{cell::Cell, rc::Rc, time::Instant};
+
+pub trait Clock {
+    fn ticks(&self) -> u64;
+}
+
+impl<T: Clock + ?Sized> Clock for &T {
+    fn ticks(&self) -> u64 {
+        (**self).ticks()
+    }
+}
+
+impl<T: Clock + ?Sized> Clock for Rc<T> {
+    fn ticks(&self) -> u64 {
+        (**self).ticks()
+    }
+}
+
+impl<T: Clock + ?Sized> Clock for Box<T> {
+    fn ticks(&self) -> u64 {
+        (**self).ticks()
+    }
+}
+
+//Milliseconds since the clock was created
+pub struct InstantClock {
+    start: Instant,
+}
+
+impl InstantClock {
+    pub fn new() -> Self {
+        InstantClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for InstantClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for InstantClock {
+    fn ticks(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+}
+
+//Only moves when told to, for tests and replays
+pub struct ManualClock {
+    ticks: Cell<u64>,
+}
+
+impl ManualClock {
+    pub fn new(start: u64) -> Self {
+        ManualClock {
+            ticks: Cell::new(start),
+        }
+    }
+
+    pub fn set(&self, ticks: u64) {
+        self.ticks.set(ticks);
+    }
+
+    pub fn advance(&self, ticks: u64) {
+        self.ticks.set(self.ticks.get() + ticks);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Clock for ManualClock {
+    fn ticks(&self) -> u64 {
+        self.ticks.get()
+    }
+}
+
+//Scales the time of another clock, a scale of 0 or a pause freezes it
+pub struct ScaledClock<C: Clock> {
+    inner: C,
+    scale: Cell<f64>,
+    paused: Cell<bool>,
+
+    previous: Cell<u64>,
+    elapsed: Cell<f64>,
+}
+
+impl<C: Clock> ScaledClock<C> {
+    pub fn new(inner: C, scale: f64) -> Self {
+        let previous = inner.ticks();
+
+        ScaledClock {
+            inner,
+            scale: Cell::new(scale.max(0.0)),
+            paused: Cell::new(false),
+            previous: Cell::new(previous),
+            elapsed: Cell::new(0.0),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale.get()
+    }
+
+    pub fn set_scale(&self, scale: f64) {
+        self.sync();
+        self.scale.set(scale.max(0.0));
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    pub fn pause(&self) {
+        self.sync();
+        self.paused.set(true);
+    }
+
+    pub fn resume(&self) {
+        self.sync();
+        self.paused.set(false);
+    }
+
+    //Banks the inner time passed so far at the current scale
+    fn sync(&self) {
+        let current = self.inner.ticks();
+        let passed = current.saturating_sub(self.previous.get());
+        self.previous.set(current);
+
+        if !self.paused.get() {
+            self.elapsed
+                .set(self.elapsed.get() + passed as f64 * self.scale.get());
+        }
+    }
+}
+
+impl<C: Clock> Clock for ScaledClock<C> {
+    fn ticks(&self) -> u64 {
+        self.sync();
+
+        self.elapsed.get() as u64
+    }
+}
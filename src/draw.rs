@@ -1,4 +1,4 @@
-use crate::loading::Ticket;
+use crate::loading::AnyTicket;
 
 pub struct DrawBatch<T, C> {
     pub context: C,
@@ -28,7 +28,7 @@ pub enum DrawType {
 }
 
 pub struct Draw {
-    pub ticket: Ticket,
+    pub ticket: AnyTicket,
     pub draw_type: DrawType,
     pub data: DrawData,
 }
@@ -2,10 +2,10 @@ use std::marker::PhantomData;
 
 use crate::{
     draw::{Draw, DrawBatch, DrawData, DrawType},
-    input::{ActionState, ActionType},
+    input::{ActionHandle, ActionState, ActionType},
     loading::{Ticket, TicketManager},
     scene::Scene,
-    utility::{Initialize, StorageType, Update, UpdateInfo, UpdateInstruction},
+    utility::{Initialize, StorageResources, StorageType, Update, UpdateInfo, UpdateInstruction},
     Response,
 };
 
@@ -16,42 +16,50 @@ pub const WINDOW_HEIGHT: u32 = 600;
 
 const SPEED: f32 = 300.0;
 
-struct Logo {
+struct Logo<T> {
     pub position: (f32, f32),
-    pub texture: Ticket,
+    pub texture: Ticket<T>,
+}
+
+//The actions the example scenes read, the app running them binds these to its devices
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Control {
+    Forward,
+    Backward,
+    Look,
+    Aim,
+    Pause,
 }
 
 struct PlayerControls {
-    forward: usize,
-    backward: usize,
-    look: usize,
-    pause: usize,
+    forward: ActionHandle,
+    backward: ActionHandle,
+    look: ActionHandle,
+    aim: ActionHandle,
+    pause: ActionHandle,
 }
 
-pub struct ExampleScene<I, S> {
-    controls: PlayerControls,
+pub struct ExampleScene<I, S, T: StorageResources> {
+    controls: Option<PlayerControls>,
     direction: (f32, f32),
+    pointer_aim: bool,
 
-    logo: Option<Logo>,
+    logo: Option<Logo<T::Texture>>,
 
-    music: Option<Ticket>,
-    oob: Option<Ticket>,
+    music: Option<Ticket<T::Music>>,
+    oob: Option<Ticket<T::Sound>>,
 
     ui: bool,
 
     phantom: PhantomData<(I, S)>,
 }
 
-impl<I, S> ExampleScene<I, S> {
+impl<I, S, T: StorageResources> ExampleScene<I, S, T> {
     pub fn new() -> Self {
         ExampleScene {
-            controls: PlayerControls {
-                forward: usize::MAX,
-                backward: usize::MAX,
-                look: usize::MAX,
-                pause: usize::MAX,
-            },
+            controls: None,
             direction: (0.0, 0.0),
+            pointer_aim: false,
 
             logo: None,
 
@@ -65,13 +73,14 @@ impl<I, S> ExampleScene<I, S> {
     }
 }
 
-impl<I, S> Scene for ExampleScene<I, S>
+impl<I, S, T> Scene for ExampleScene<I, S, T>
 where
     S: TicketManager<StorageType, StorageType, String, str>,
+    T: StorageResources,
 {
     type Key = String;
-    type Initialize = Initialize<I, S, ()>;
-    type Update = Update<I, ()>;
+    type Initialize = Initialize<I, S, (), Control>;
+    type Update = Update<I, (), Control>;
     type Message = String;
     type Instruction = UpdateInstruction;
     type Draw = ();
@@ -79,10 +88,14 @@ where
 
     fn initialize(&mut self, init: &mut Self::Initialize) {
         let input = init.input.borrow();
-        self.controls.forward = input.users[0].get_index_by_key("Forward").unwrap();
-        self.controls.backward = input.users[0].get_index_by_key("Backward").unwrap();
-        self.controls.look = input.users[0].get_index_by_key("Look").unwrap();
-        self.controls.pause = input.users[0].get_index_by_key("Pause").unwrap();
+        let handle = |control| input.users[0].get_handle_by_key(&control).unwrap();
+        self.controls = Some(PlayerControls {
+            forward: handle(Control::Forward),
+            backward: handle(Control::Backward),
+            look: handle(Control::Look),
+            aim: handle(Control::Aim),
+            pause: handle(Control::Pause),
+        });
 
         let storage = init.storage.borrow();
         self.logo = Some(Logo {
@@ -114,48 +127,60 @@ where
 
         let info = update.info.borrow();
         for info in info.iter() {
-            match info {
-                UpdateInfo::MusicStopped => actions.push(Response::Instruction(
-                    UpdateInstruction::PlayMusic(self.music.unwrap(), -1, 0.25),
-                )),
+            if let UpdateInfo::MusicStopped = info {
+                actions.push(Response::Instruction(UpdateInstruction::PlayMusic(
+                    self.music.as_ref().unwrap().erase(),
+                    -1,
+                    0.25,
+                )));
             }
         }
 
-        if let Some(logo) = &mut self.logo {
+        if let (Some(logo), Some(controls)) = (&mut self.logo, &self.controls) {
             let position = logo.position;
 
             self.direction = (0.0, 1.0);
 
             let input = update.input.borrow();
 
-            if let ActionType::Analog { x, y } = input.users[0]
-                .get_action_by_index(self.controls.look)
-                .unwrap()
+            //Whichever of the stick and the mouse moved last does the aiming
+            if let ActionType::Analog { x, y } = input.get_action_by_handle(controls.look).unwrap()
+            {
+                if x != 0.0 || y != 0.0 {
+                    self.pointer_aim = false;
+                    self.direction = (x, y);
+                }
+            }
+
+            if let ActionType::Pointer(pointer) = input.get_action_by_handle(controls.aim).unwrap()
             {
-                //Dirty toggle between mouse and stick
-                if x.abs() >= 1.1 || y.abs() >= 1.1 {
-                    let (relative_x, relative_y) = (x - position.0, y - position.1);
+                if pointer.moved() {
+                    self.pointer_aim = true;
+                }
+
+                if self.pointer_aim && pointer.in_window {
+                    let (relative_x, relative_y) = (pointer.x - position.0, pointer.y - position.1);
                     let length = f32::sqrt(relative_x * relative_x + relative_y * relative_y);
 
-                    self.direction = (relative_x / length, relative_y / length);
-                } else {
-                    self.direction = (x, y);
+                    if length > 0.0 {
+                        self.direction = (relative_x / length, relative_y / length);
+                    }
                 }
             }
 
             let multiplier = SPEED * delta as f32;
             let direction = (self.direction.0 * multiplier, self.direction.1 * multiplier);
 
-            if input.users[0]
-                .get_action_by_index(self.controls.forward)
+            if input
+                .get_action_by_handle(controls.forward)
                 .unwrap()
                 .is_down()
             {
                 logo.position = (position.0 + direction.0, position.1 + direction.1);
             }
 
-            if input.users[0]
-                .get_action_by_index(self.controls.backward)
+            if input
+                .get_action_by_handle(controls.backward)
                 .unwrap()
                 .is_down()
             {
@@ -169,7 +194,7 @@ where
             {
                 actions.push(Response::Message("UI".to_string(), "Collision".to_string()));
                 actions.push(Response::Instruction(UpdateInstruction::PlaySound(
-                    self.oob.unwrap(),
+                    self.oob.as_ref().unwrap().erase(),
                     1.0,
                 )));
             }
@@ -179,10 +204,7 @@ where
                 logo.position.1.clamp(0.0, WINDOW_HEIGHT as f32),
             );
 
-            match input.users[0]
-                .get_action_by_index(self.controls.pause)
-                .unwrap()
-            {
+            match input.get_action_by_handle(controls.pause).unwrap() {
                 ActionType::Digital(s) => {
                     if s == ActionState::Pressed {
                         self.ui = !self.ui;
@@ -207,7 +229,7 @@ where
                 (self.direction.1.atan2(self.direction.0) * 180.0 / std::f32::consts::PI) + 90.0;
 
             batch.instructions.push(Draw {
-                ticket: logo.texture,
+                ticket: logo.texture.erase(),
                 draw_type: DrawType::Texture,
                 data: DrawData::draw_rotated_at(
                     logo.position.0,
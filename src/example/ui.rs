@@ -2,22 +2,23 @@ use std::marker::PhantomData;
 
 use crate::{
     draw::{Draw, DrawBatch, DrawColor, DrawData, DrawType},
+    example::Control,
     loading::{Ticket, TicketManager},
     scene::Scene,
-    utility::{Initialize, StorageType, Update, UpdateInstruction},
+    utility::{Initialize, StorageResources, StorageType, Update, UpdateInstruction},
     Response,
 };
 
-pub struct UIScene<I, S> {
+pub struct UIScene<I, S, T: StorageResources> {
     input: (String, String, String),
-    font: Option<Ticket>,
+    font: Option<Ticket<T::Font>>,
 
     collision: u32,
 
     phantom: PhantomData<(I, S)>,
 }
 
-impl<I, S> UIScene<I, S> {
+impl<I, S, T: StorageResources> UIScene<I, S, T> {
     pub fn new() -> Self {
         UIScene {
             input: (
@@ -34,13 +35,14 @@ impl<I, S> UIScene<I, S> {
     }
 }
 
-impl<I, S> Scene for UIScene<I, S>
+impl<I, S, T> Scene for UIScene<I, S, T>
 where
     S: TicketManager<StorageType, StorageType, String, str>,
+    T: StorageResources,
 {
     type Key = String;
-    type Initialize = Initialize<I, S, ()>;
-    type Update = Update<I, ()>;
+    type Initialize = Initialize<I, S, (), Control>;
+    type Update = Update<I, (), Control>;
     type Message = String;
     type Instruction = UpdateInstruction;
     type Draw = ();
@@ -72,8 +74,9 @@ where
             a: 0.5,
         };
 
-        match self.font {
-            Some(f) => {
+        match &self.font {
+            Some(font) => {
+                let f = font.erase();
                 batch.instructions.push(Draw {
                     ticket: f,
                     data: DrawData::draw_centered_at((super::WINDOW_WIDTH / 2) as f32, 100.0),
@@ -0,0 +1,430 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    str::FromStr,
+};
+
+use super::InputValue;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Binding<C> {
+    Single(C),
+    Chord(Chord<C>),
+    Composite(Composite<C>),
+}
+
+//Every modifier has to be held for the key to count
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chord<C> {
+    pub modifiers: Vec<C>,
+    pub key: C,
+    pub exclusive: bool,
+}
+
+impl<C> Chord<C> {
+    pub fn new(modifiers: Vec<C>, key: C) -> Self {
+        Chord {
+            modifiers,
+            key,
+            exclusive: false,
+        }
+    }
+
+    //Also requires that no modifier outside of the chord is held
+    pub fn exclusive(modifiers: Vec<C>, key: C) -> Self {
+        Chord {
+            modifiers,
+            key,
+            exclusive: true,
+        }
+    }
+}
+
+//What a composite does when both keys of an axis are held
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Opposites {
+    LastWins,
+    FirstWins,
+    Cancel,
+}
+
+//Digital inputs pushing an axis or stick, so keys can drive the same action as a thumbstick.
+//Negative y is up, matching most stick conventions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Composite<C> {
+    pub x_negative: Option<C>,
+    pub x_positive: Option<C>,
+    pub y_negative: Option<C>,
+    pub y_positive: Option<C>,
+    pub opposites: Opposites,
+}
+
+impl<C> Composite<C> {
+    pub fn axis(negative: C, positive: C) -> Self {
+        Composite {
+            x_negative: Some(negative),
+            x_positive: Some(positive),
+            y_negative: None,
+            y_positive: None,
+            opposites: Opposites::LastWins,
+        }
+    }
+
+    pub fn directions(left: C, right: C, up: C, down: C) -> Self {
+        Composite {
+            x_negative: Some(left),
+            x_positive: Some(right),
+            y_negative: Some(up),
+            y_positive: Some(down),
+            opposites: Opposites::LastWins,
+        }
+    }
+
+    pub fn with_opposites(mut self, opposites: Opposites) -> Self {
+        self.opposites = opposites;
+        self
+    }
+
+    fn is_axis(&self) -> bool {
+        self.y_negative.is_none() && self.y_positive.is_none()
+    }
+}
+
+impl<C> Composite<C>
+where
+    C: Hash + Eq,
+{
+    fn inputs(&self) -> impl Iterator<Item = &C> {
+        [
+            &self.x_negative,
+            &self.x_positive,
+            &self.y_negative,
+            &self.y_positive,
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    //Diagonals are normalized so they are no faster than a single direction
+    fn value(&self, held: &Held<C>) -> InputValue {
+        let x = self.direction(&self.x_negative, &self.x_positive, held);
+
+        if self.is_axis() {
+            return InputValue::Axis(x);
+        }
+
+        let y = self.direction(&self.y_negative, &self.y_positive, held);
+        let length = (x * x + y * y).sqrt();
+
+        match length > 1.0 {
+            true => InputValue::Analog {
+                x: x / length,
+                y: y / length,
+            },
+            false => InputValue::Analog { x, y },
+        }
+    }
+
+    fn direction(&self, negative: &Option<C>, positive: &Option<C>, held: &Held<C>) -> f32 {
+        match (
+            negative.as_ref().filter(|c| held.is_down(c)),
+            positive.as_ref().filter(|c| held.is_down(c)),
+        ) {
+            (Some(_), None) => -1.0,
+            (None, Some(_)) => 1.0,
+            (Some(n), Some(p)) => match self.opposites {
+                Opposites::Cancel => 0.0,
+                Opposites::LastWins if held.pressed_at(n) > held.pressed_at(p) => -1.0,
+                Opposites::LastWins => 1.0,
+                Opposites::FirstWins if held.pressed_at(n) < held.pressed_at(p) => -1.0,
+                Opposites::FirstWins => 1.0,
+            },
+            (None, None) => 0.0,
+        }
+    }
+}
+
+//Latest raw value of every input, and the order they were pressed in
+#[derive(Clone)]
+pub(super) struct Held<C> {
+    values: HashMap<C, InputValue>,
+    pressed: HashMap<C, u64>,
+    presses: u64,
+}
+
+impl<C> Held<C> {
+    pub(super) fn new() -> Self {
+        Held {
+            values: HashMap::new(),
+            pressed: HashMap::new(),
+            presses: 0,
+        }
+    }
+}
+
+impl<C> Held<C>
+where
+    C: Hash + Eq + Clone,
+{
+    pub(super) fn insert(&mut self, input: &C, value: InputValue) {
+        if value.is_down() && !self.is_down(input) {
+            self.presses += 1;
+            self.pressed.insert(input.clone(), self.presses);
+        }
+
+        self.values.insert(input.clone(), value);
+    }
+
+    pub(super) fn turned_wheels(&self) -> Vec<C> {
+        self.values
+            .iter()
+            .filter(|(_, v)| matches!(v, InputValue::PointerWheel(w) if *w != 0.0))
+            .map(|(input, _)| input.clone())
+            .collect()
+    }
+
+    //A copy where the inputs were never pressed
+    pub(super) fn without(&self, inputs: &[&C]) -> Held<C> {
+        let mut held = self.clone();
+        for input in inputs {
+            held.values.remove(*input);
+            held.pressed.remove(*input);
+        }
+
+        held
+    }
+}
+
+impl<C> Held<C>
+where
+    C: Hash + Eq,
+{
+    pub(super) fn get(&self, input: &C) -> Option<InputValue> {
+        self.values.get(input).copied()
+    }
+
+    pub(super) fn is_down(&self, input: &C) -> bool {
+        self.values.get(input).is_some_and(|v| v.is_down())
+    }
+
+    fn pressed_at(&self, input: &C) -> u64 {
+        self.pressed.get(input).copied().unwrap_or(0)
+    }
+}
+
+impl<C> From<C> for Binding<C> {
+    fn from(command: C) -> Self {
+        Binding::Single(command)
+    }
+}
+
+impl<C> From<Chord<C>> for Binding<C> {
+    fn from(chord: Chord<C>) -> Self {
+        Binding::Chord(chord)
+    }
+}
+
+impl<C> From<Composite<C>> for Binding<C> {
+    fn from(composite: Composite<C>) -> Self {
+        Binding::Composite(composite)
+    }
+}
+
+//Written as Shift+Up, with a leading ! for exclusive chords.
+//Composites are written as [A/D] or [A/D/W/S], with _ for a missing direction
+//and ~first or ~cancel when they do not use last wins.
+impl<C: fmt::Display> fmt::Display for Binding<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Binding::Single(c) => write!(f, "{}", c),
+            Binding::Chord(chord) => {
+                if chord.exclusive {
+                    write!(f, "!")?;
+                }
+
+                for m in chord.modifiers.iter() {
+                    write!(f, "{}+", m)?;
+                }
+
+                write!(f, "{}", chord.key)
+            }
+            Binding::Composite(composite) => {
+                let directions = match composite.is_axis() {
+                    true => vec![&composite.x_negative, &composite.x_positive],
+                    false => vec![
+                        &composite.x_negative,
+                        &composite.x_positive,
+                        &composite.y_negative,
+                        &composite.y_positive,
+                    ],
+                };
+
+                let directions: Vec<String> = directions
+                    .into_iter()
+                    .map(|d| match d {
+                        Some(c) => c.to_string(),
+                        None => "_".to_string(),
+                    })
+                    .collect();
+                write!(f, "[{}]", directions.join("/"))?;
+
+                match composite.opposites {
+                    Opposites::LastWins => Ok(()),
+                    Opposites::FirstWins => write!(f, "~first"),
+                    Opposites::Cancel => write!(f, "~cancel"),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseBindingError(pub String);
+
+impl<C: FromStr> FromStr for Binding<C> {
+    type Err = ParseBindingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseBindingError(s.to_string());
+
+        if let Some(rest) = s.strip_prefix('[') {
+            let (directions, opposites) = rest.split_once(']').ok_or_else(error)?;
+
+            let opposites = match opposites.trim() {
+                "" => Opposites::LastWins,
+                "~first" => Opposites::FirstWins,
+                "~cancel" => Opposites::Cancel,
+                _ => return Err(error()),
+            };
+
+            let mut directions = directions
+                .split('/')
+                .map(|d| match d.trim() {
+                    "_" => Ok(None),
+                    d => d.parse().map(Some).map_err(|_| error()),
+                })
+                .collect::<Result<Vec<Option<C>>, ParseBindingError>>()?
+                .into_iter();
+
+            let composite = match directions.len() {
+                2 | 4 => Composite {
+                    x_negative: directions.next().flatten(),
+                    x_positive: directions.next().flatten(),
+                    y_negative: directions.next().flatten(),
+                    y_positive: directions.next().flatten(),
+                    opposites,
+                },
+                _ => return Err(error()),
+            };
+
+            return Ok(Binding::Composite(composite));
+        }
+
+        let (exclusive, s) = match s.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let (modifiers, key) = match s.rsplit_once('+') {
+            Some((modifiers, key)) => (
+                modifiers
+                    .split('+')
+                    .map(|c| c.trim().parse().map_err(|_| error()))
+                    .collect::<Result<Vec<C>, ParseBindingError>>()?,
+                key,
+            ),
+            None => (Vec::new(), s),
+        };
+        let key = key.trim().parse().map_err(|_| error())?;
+
+        match modifiers.is_empty() && !exclusive {
+            true => Ok(Binding::Single(key)),
+            false => Ok(Binding::Chord(Chord {
+                modifiers,
+                key,
+                exclusive,
+            })),
+        }
+    }
+}
+
+impl<C> Binding<C>
+where
+    C: Hash + Eq,
+{
+    //The input whose value drives the action, composites have none
+    pub fn key(&self) -> Option<&C> {
+        match self {
+            Binding::Single(c) => Some(c),
+            Binding::Chord(chord) => Some(&chord.key),
+            Binding::Composite(_) => None,
+        }
+    }
+
+    pub fn inputs(&self) -> Vec<&C> {
+        match self {
+            Binding::Single(c) => vec![c],
+            Binding::Chord(chord) => chord
+                .modifiers
+                .iter()
+                .chain(std::iter::once(&chord.key))
+                .collect(),
+            Binding::Composite(composite) => composite.inputs().collect(),
+        }
+    }
+
+    pub fn modifiers(&self) -> &[C] {
+        match self {
+            Binding::Chord(chord) => &chord.modifiers,
+            _ => &[],
+        }
+    }
+
+    pub fn is_exclusive(&self) -> bool {
+        match self {
+            Binding::Chord(chord) => chord.exclusive,
+            _ => false,
+        }
+    }
+
+    //The key's value, if the rest of the binding is satisfied
+    pub(super) fn value(&self, held: &Held<C>, modifiers: &HashSet<C>) -> Option<InputValue> {
+        let key = match self {
+            Binding::Composite(composite) => return Some(composite.value(held)),
+            Binding::Single(key) => key,
+            Binding::Chord(chord) => &chord.key,
+        };
+
+        if !self.modifiers().iter().all(|m| held.is_down(m)) {
+            return None;
+        }
+
+        if self.is_exclusive()
+            && modifiers
+                .iter()
+                .any(|m| held.is_down(m) && m != key && !self.modifiers().contains(m))
+        {
+            return None;
+        }
+
+        held.get(key)
+    }
+
+    pub(super) fn is_active(&self, held: &Held<C>, modifiers: &HashSet<C>) -> bool {
+        self.value(held, modifiers).is_some_and(|v| v.is_down())
+    }
+
+    //True when this chord needs strictly more inputs held than the other binding,
+    //so Shift+Up is more specific than Up
+    pub(super) fn is_more_specific(&self, other: &Binding<C>) -> bool {
+        if !matches!(self, Binding::Chord(_)) || matches!(other, Binding::Composite(_)) {
+            return false;
+        }
+
+        let inputs = self.inputs();
+        let other = other.inputs();
+
+        inputs.len() > other.len() && other.iter().all(|c| inputs.contains(c))
+    }
+}
@@ -0,0 +1,155 @@
+use std::{borrow::Cow, hash::Hash};
+
+use super::{
+    binding::{Binding, Held},
+    ActionHandle, ActionKey, ActionType, InputError, InputMap, UserHandle,
+};
+
+//Every map starts with this context at the bottom of its stack
+pub const DEFAULT_CONTEXT: &str = "default";
+
+//A named set of bindings. Only the topmost active context that binds an input receives it,
+//unless the contexts above let unbound inputs pass through to the ones below.
+pub struct InputContext {
+    pub name: String,
+    pub pass_through: bool,
+}
+
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+{
+    //Returns the index of the context, which is kept if it already exists
+    pub fn add_context(&mut self, name: &str, pass_through: bool) -> usize {
+        match self.context_index(name) {
+            Ok(index) => {
+                self.contexts[index].pass_through = pass_through;
+                index
+            }
+            Err(_) => {
+                self.contexts.push(InputContext {
+                    name: name.to_string(),
+                    pass_through,
+                });
+
+                self.contexts.len() - 1
+            }
+        }
+    }
+
+    pub fn add_action_in_context<B>(
+        &mut self,
+        context: &str,
+        user: UserHandle,
+        key: A,
+        commands: Vec<B>,
+        action: ActionType,
+    ) -> Result<ActionHandle, InputError>
+    where
+        A: ActionKey,
+        B: Into<Binding<C>>,
+    {
+        let context = self.context_index(context)?;
+
+        self.add_bound_action(context, user, key, commands, action)
+    }
+
+    //Moves the context to the top if it is already active.
+    //The default context stays pinned at the bottom, so pushing it does nothing.
+    pub fn push_context(&mut self, name: &str) -> Result<(), InputError> {
+        let index = self.context_index(name)?;
+        if index == 0 {
+            return Ok(());
+        }
+
+        self.stack.retain(|c| *c != index);
+        self.stack.push(index);
+
+        self.refresh_all()
+    }
+
+    //The default context is never popped
+    pub fn pop_context(&mut self) -> Result<Option<String>, InputError> {
+        if self.stack.len() <= 1 {
+            return Ok(None);
+        }
+
+        let popped = self.stack.pop().map(|c| self.contexts[c].name.clone());
+        self.refresh_all()?;
+
+        Ok(popped)
+    }
+
+    pub fn remove_context(&mut self, name: &str) -> Result<(), InputError> {
+        let index = self.context_index(name)?;
+        if index == 0 {
+            return Ok(());
+        }
+
+        self.stack.retain(|c| *c != index);
+
+        self.refresh_all()
+    }
+
+    pub fn is_context_active(&self, name: &str) -> bool {
+        self.context_index(name)
+            .is_ok_and(|index| self.stack.contains(&index))
+    }
+
+    //From the bottom of the stack to the top
+    pub fn active_contexts(&self) -> impl Iterator<Item = &str> {
+        self.stack.iter().map(|c| self.contexts[*c].name.as_str())
+    }
+
+    pub fn context_index(&self, name: &str) -> Result<usize, InputError> {
+        self.contexts
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or(InputError::UnknownContext)
+    }
+
+    //The context whose bindings receive the input, if any
+    pub(super) fn route(&self, input: &C) -> Option<usize> {
+        let bound = self.index.get(input);
+
+        for context in self.stack.iter().rev() {
+            if bound.is_some_and(|b| b.iter().any(|i| self.commands[*i].context == *context)) {
+                return Some(*context);
+            }
+
+            if !self.contexts[*context].pass_through {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    //What the binding sees of its user's inputs. Inputs routed to another context read as
+    //released, so a composite keeps the directions no context above it binds.
+    //None when none of its inputs reach the binding's context.
+    pub(super) fn live_held(&self, index: usize, binding: &Binding<C>) -> Option<Cow<'_, Held<C>>> {
+        let command = &self.commands[index];
+        let held = &self.user_held[command.user_index];
+
+        let (live, shadowed): (Vec<&C>, Vec<&C>) = binding
+            .inputs()
+            .into_iter()
+            .partition(|input| self.route(input) == Some(command.context));
+
+        match (live.is_empty(), shadowed.is_empty()) {
+            (true, _) => None,
+            (false, true) => Some(Cow::Borrowed(held)),
+            (false, false) => Some(Cow::Owned(held.without(&shadowed))),
+        }
+    }
+
+    //Actions in contexts that just got shadowed are released
+    fn refresh_all(&mut self) -> Result<(), InputError> {
+        for i in 0..self.commands.len() {
+            self.refresh(i)?;
+        }
+
+        Ok(())
+    }
+}
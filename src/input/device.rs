@@ -0,0 +1,258 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{InputError, InputMap, InputValue, UserHandle};
+
+//Whatever the backend uses to tell gamepads and other devices apart
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DeviceId(pub u64);
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeviceEvent {
+    Joined { user: UserHandle, device: DeviceId },
+    Disconnected { user: UserHandle, device: DeviceId },
+    Reconnected { user: UserHandle, device: DeviceId },
+}
+
+pub(super) struct Devices<C> {
+    owners: HashMap<DeviceId, usize>,
+    //Users that lost their last device, oldest first, with the device they lost
+    waiting: Vec<(usize, DeviceId)>,
+    join: Option<C>,
+    events: Vec<DeviceEvent>,
+    //The last value of every input from each claimed device
+    held: HashMap<DeviceId, HashMap<C, InputValue>>,
+}
+
+impl<C> Devices<C> {
+    pub fn new() -> Self {
+        Devices {
+            owners: HashMap::new(),
+            waiting: Vec::new(),
+            join: None,
+            events: Vec::new(),
+            held: HashMap::new(),
+        }
+    }
+
+    pub fn settle_wheels(&mut self) {
+        for value in self
+            .held
+            .values_mut()
+            .flat_map(|inputs| inputs.values_mut())
+        {
+            if let InputValue::PointerWheel(_) = value {
+                *value = InputValue::PointerWheel(0.0);
+            }
+        }
+    }
+
+    pub fn forget_user(&mut self, user: usize) {
+        self.owners.retain(|_, owner| *owner != user);
+        self.waiting.retain(|(u, _)| *u != user);
+
+        let owners = &self.owners;
+        self.held.retain(|device, _| owners.contains_key(device));
+    }
+}
+
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+{
+    //Inputs from the device only reach this user from now on
+    pub fn claim_device(&mut self, user: UserHandle, device: DeviceId) -> Result<(), InputError> {
+        let user = self.check_user(user)?;
+
+        match self.devices.owners.get(&device) {
+            Some(owner) if *owner == user => Ok(()),
+            Some(_) => Err(InputError::DeviceAlreadyClaimed),
+            None => {
+                self.give(user, device);
+
+                Ok(())
+            }
+        }
+    }
+
+    //Everything the device held is let go
+    pub fn release_device(&mut self, device: DeviceId) -> Result<(), InputError> {
+        if let Some(user) = self.devices.owners.remove(&device) {
+            self.release_inputs(user, device)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn device_owner(&self, device: DeviceId) -> Option<UserHandle> {
+        self.devices
+            .owners
+            .get(&device)
+            .map(|user| self.users[*user].user())
+    }
+
+    pub fn user_devices(&self, user: UserHandle) -> Result<Vec<DeviceId>, InputError> {
+        Ok(self.devices_of(self.check_user(user)?))
+    }
+
+    pub fn is_user_disconnected(&self, user: UserHandle) -> Result<bool, InputError> {
+        Ok(self.is_waiting(self.check_user(user)?))
+    }
+
+    fn devices_of(&self, user: usize) -> Vec<DeviceId> {
+        self.devices
+            .owners
+            .iter()
+            .filter(|(_, owner)| **owner == user)
+            .map(|(device, _)| *device)
+            .collect()
+    }
+
+    fn is_waiting(&self, user: usize) -> bool {
+        self.devices.waiting.iter().any(|(u, _)| *u == user)
+    }
+
+    //While set, pressing the input on an unclaimed device hands it
+    //to the first user without any devices
+    pub fn set_join_input(&mut self, input: Option<C>) {
+        self.devices.join = input;
+    }
+
+    //A device coming back with the id it left with goes straight back to its user
+    pub fn connect_device(&mut self, device: DeviceId) {
+        let waiting = self.devices.waiting.iter().find(|(_, d)| *d == device);
+
+        if let Some((user, _)) = waiting.copied() {
+            self.give(user, device);
+        }
+    }
+
+    //Users left without devices wait for one to reconnect,
+    //or for any unclaimed device to press something
+    pub fn disconnect_device(&mut self, device: DeviceId) -> Result<(), InputError> {
+        let user = match self.devices.owners.remove(&device) {
+            Some(user) => user,
+            None => return Ok(()),
+        };
+
+        if self.devices_of(user).is_empty() {
+            self.devices.waiting.push((user, device));
+            self.devices.events.push(DeviceEvent::Disconnected {
+                user: self.users[user].user(),
+                device,
+            });
+        }
+
+        self.release_inputs(user, device)
+    }
+
+    //Like process, for inputs that came from a device
+    pub fn process_from(
+        &mut self,
+        device: DeviceId,
+        command: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        self.live(Some(device), command, value)?;
+
+        self.route_device(device, command, value)
+    }
+
+    pub(super) fn route_device(
+        &mut self,
+        device: DeviceId,
+        command: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        match self.devices.owners.get(&device) {
+            Some(user) => {
+                let user = *user;
+                self.devices
+                    .held
+                    .entry(device)
+                    .or_default()
+                    .insert(command.clone(), value);
+
+                self.apply_input(Some(user), command, value)
+            }
+            None => {
+                //The press that assigns the device does not reach any action
+                if value.is_down() {
+                    self.assign(device, command);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    //Joins, disconnects and reconnects since the last call, oldest first
+    pub fn take_device_events(&mut self) -> Vec<DeviceEvent> {
+        self.devices.events.drain(..).collect()
+    }
+
+    fn assign(&mut self, device: DeviceId, command: &C) {
+        if let Some((user, _)) = self.devices.waiting.first().copied() {
+            self.give(user, device);
+
+            return;
+        }
+
+        if self.devices.join.as_ref() != Some(command) {
+            return;
+        }
+
+        let free = (0..self.users.len()).find(|u| {
+            self.is_slot_used(*u)
+                && !self.devices.owners.values().any(|owner| owner == u)
+                && !self.is_waiting(*u)
+        });
+
+        if let Some(user) = free {
+            self.devices.owners.insert(device, user);
+            self.devices.events.push(DeviceEvent::Joined {
+                user: self.users[user].user(),
+                device,
+            });
+        }
+    }
+
+    fn give(&mut self, user: usize, device: DeviceId) {
+        self.devices.owners.insert(device, user);
+
+        if let Some(position) = self.devices.waiting.iter().position(|(u, _)| *u == user) {
+            self.devices.waiting.remove(position);
+            self.devices.events.push(DeviceEvent::Reconnected {
+                user: self.users[user].user(),
+                device,
+            });
+        }
+    }
+
+    //Lets go of what the device held, unless the user's other devices
+    //or the shared ones still hold the same input
+    fn release_inputs(&mut self, user: usize, device: DeviceId) -> Result<(), InputError> {
+        let inputs = self.devices.held.remove(&device).unwrap_or_default();
+
+        for (input, value) in inputs {
+            let remaining = self
+                .devices
+                .held
+                .iter()
+                .filter(|(d, _)| self.devices.owners.get(d) == Some(&user))
+                .find_map(|(_, held)| held.get(&input).filter(|v| v.is_down()).copied())
+                .or_else(|| self.held.get(&input));
+
+            if let Some(value) = remaining.or_else(|| value.rest()) {
+                self.user_held[user].insert(&input, value);
+            }
+        }
+
+        for i in 0..self.commands.len() {
+            if self.commands[i].user_index == user {
+                self.refresh(i)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use super::{InputError, InputMap, UserHandle};
+
+//Strengths in 0..=1 for the two motors most controllers have
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Motors {
+    pub low: f32,
+    pub high: f32,
+}
+
+//Seconds to ramp up at the start and down at the end of an effect
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Envelope {
+    pub attack: f64,
+    pub release: f64,
+}
+
+impl Envelope {
+    pub fn new(attack: f64, release: f64) -> Self {
+        Envelope { attack, release }
+    }
+
+    fn scale(&self, elapsed: f64, duration: f64) -> f32 {
+        let up = match self.attack > 0.0 {
+            true => (elapsed / self.attack).min(1.0),
+            false => 1.0,
+        };
+        let down = match self.release > 0.0 {
+            true => ((duration - elapsed) / self.release).clamp(0.0, 1.0),
+            false => 1.0,
+        };
+
+        up.min(down) as f32
+    }
+}
+
+//An infinite duration rumbles until stopped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rumble {
+    pub motors: Motors,
+    pub duration: f64,
+    pub envelope: Envelope,
+}
+
+impl Rumble {
+    pub fn new(low: f32, high: f32, duration: f64) -> Self {
+        Rumble {
+            motors: Motors { low, high },
+            duration,
+            envelope: Envelope::default(),
+        }
+    }
+
+    pub fn with_envelope(mut self, attack: f64, release: f64) -> Self {
+        self.envelope = Envelope::new(attack, release);
+        self
+    }
+
+    fn at(&self, elapsed: f64) -> Motors {
+        let scale = self.envelope.scale(elapsed, self.duration);
+
+        Motors {
+            low: self.motors.low * scale,
+            high: self.motors.high * scale,
+        }
+    }
+}
+
+//Overlapping effects are merged by taking the strongest value of each motor
+struct HapticQueue {
+    effects: Vec<(Rumble, f64)>,
+    motors: Motors,
+}
+
+impl HapticQueue {
+    fn advance(&mut self, delta: f64, intensity: f32) {
+        let mut motors = Motors::default();
+
+        for (rumble, elapsed) in self.effects.iter_mut() {
+            let current = rumble.at(*elapsed);
+            motors.low = motors.low.max(current.low);
+            motors.high = motors.high.max(current.high);
+
+            *elapsed += delta;
+        }
+
+        self.effects
+            .retain(|(rumble, elapsed)| *elapsed < rumble.duration);
+
+        self.motors = Motors {
+            low: (motors.low * intensity).clamp(0.0, 1.0),
+            high: (motors.high * intensity).clamp(0.0, 1.0),
+        };
+    }
+}
+
+pub(super) struct Haptics {
+    queues: Vec<HapticQueue>,
+    intensity: f32,
+}
+
+impl Haptics {
+    pub fn new() -> Self {
+        Haptics {
+            queues: Vec::new(),
+            intensity: 1.0,
+        }
+    }
+
+    pub fn add_user(&mut self) {
+        self.queues.push(HapticQueue {
+            effects: Vec::new(),
+            motors: Motors::default(),
+        });
+    }
+
+    pub fn clear(&mut self, user: usize) {
+        if let Some(queue) = self.queues.get_mut(user) {
+            queue.effects.clear();
+            queue.motors = Motors::default();
+        }
+    }
+
+    pub fn advance(&mut self, delta: f64) {
+        for queue in self.queues.iter_mut() {
+            queue.advance(delta, self.intensity);
+        }
+    }
+}
+
+impl<C, A> InputMap<C, A> {
+    //Starts with the next advance
+    pub fn rumble(&mut self, user: UserHandle, rumble: Rumble) -> Result<(), InputError> {
+        self.haptic_queue(user)?.effects.push((rumble, 0.0));
+
+        Ok(())
+    }
+
+    pub fn stop_rumble(&mut self, user: UserHandle) -> Result<(), InputError> {
+        self.haptic_queue(user)?.effects.clear();
+
+        Ok(())
+    }
+
+    //Scales every effect, zero turns rumble off
+    pub fn set_haptic_intensity(&mut self, intensity: f32) {
+        self.haptics.intensity = intensity.max(0.0);
+    }
+
+    pub fn haptic_intensity(&self) -> f32 {
+        self.haptics.intensity
+    }
+
+    //What the host should set the user's motors to for this update
+    pub fn motors(&self, user: UserHandle) -> Result<Motors, InputError> {
+        Ok(self.haptics.queues[self.check_user(user)?].motors)
+    }
+
+    fn haptic_queue(&mut self, user: UserHandle) -> Result<&mut HapticQueue, InputError> {
+        let user = self.check_user(user)?;
+
+        Ok(&mut self.haptics.queues[user])
+    }
+}
@@ -0,0 +1,1235 @@
+use log::warn;
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+pub mod binding;
+pub mod context;
+pub mod device;
+pub mod haptics;
+pub mod pointer;
+pub mod profile;
+pub mod recording;
+pub mod response;
+pub mod sequence;
+pub mod text;
+pub mod timing;
+pub mod touch;
+
+use binding::{Binding, Chord, Held};
+use context::{InputContext, DEFAULT_CONTEXT};
+use device::Devices;
+use haptics::Haptics;
+use pointer::Pointer;
+use recording::Recorder;
+use response::AxisResponse;
+use sequence::{Sequence, SequenceBuffer, SequenceEdge};
+use text::TextInput;
+use timing::{GestureConfig, Timing};
+use touch::Touches;
+
+//How far an axis or stick has to be pushed to count as a held button
+const DIGITAL_THRESHOLD: f32 = 0.5;
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum ActionState {
+    Down,
+    Pressed,
+    Released,
+    Up,
+}
+
+impl ActionState {
+    pub fn is_down(&self) -> bool {
+        *self == ActionState::Down || *self == ActionState::Pressed
+    }
+
+    pub fn is_delta(&self) -> bool {
+        *self == ActionState::Pressed || *self == ActionState::Released
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ActionType {
+    Digital(ActionState),
+    Axis(f32),
+    Analog { x: f32, y: f32 },
+    Pointer(Pointer),
+}
+
+impl ActionType {
+    pub fn is_down(&self) -> bool {
+        match self {
+            ActionType::Digital(state) => ActionState::is_down(&state),
+            _ => false,
+        }
+    }
+
+    pub fn update(old: ActionType, new: ActionType) -> ActionType {
+        match new {
+            ActionType::Digital(new_action) => match old {
+                ActionType::Digital(old_action) => {
+                    match (old_action.is_down(), new_action.is_down()) {
+                        (true, true) => ActionType::Digital(ActionState::Down),
+                        (true, false) => ActionType::Digital(ActionState::Released),
+                        (false, true) => ActionType::Digital(ActionState::Pressed),
+                        (false, false) => ActionType::Digital(ActionState::Up),
+                    }
+                }
+                _ => match new_action {
+                    ActionState::Up => old,
+                    _ => new,
+                },
+            },
+            _ => new,
+        }
+    }
+}
+
+//A raw value reported by a device for a single command
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputValue {
+    Digital(bool),
+    Axis(f32),
+    Analog { x: f32, y: f32 },
+    PointerPosition { x: f32, y: f32 },
+    PointerWheel(f32),
+    PointerInWindow(bool),
+}
+
+impl InputValue {
+    //Wheel ticks are impulses, they never hold anything down
+    pub fn is_down(&self) -> bool {
+        match *self {
+            InputValue::Digital(down) | InputValue::PointerInWindow(down) => down,
+            InputValue::PointerPosition { .. } | InputValue::PointerWheel(_) => false,
+            _ => self.magnitude() >= DIGITAL_THRESHOLD,
+        }
+    }
+
+    pub fn axis(&self) -> f32 {
+        match *self {
+            InputValue::Digital(down) => match down {
+                true => 1.0,
+                false => 0.0,
+            },
+            InputValue::Axis(v) | InputValue::PointerWheel(v) => v,
+            InputValue::Analog { x, .. } | InputValue::PointerPosition { x, .. } => x,
+            InputValue::PointerInWindow(_) => 0.0,
+        }
+    }
+
+    pub fn analog(&self) -> (f32, f32) {
+        match *self {
+            InputValue::Digital(_) | InputValue::PointerInWindow(_) => (0.0, 0.0),
+            InputValue::Axis(v) => (v, 0.0),
+            InputValue::PointerWheel(v) => (0.0, v),
+            InputValue::Analog { x, y } | InputValue::PointerPosition { x, y } => (x, y),
+        }
+    }
+
+    //The value an input goes back to once nothing is pushing it
+    pub(super) fn rest(&self) -> Option<InputValue> {
+        match self {
+            InputValue::Digital(_) => Some(InputValue::Digital(false)),
+            InputValue::Axis(_) => Some(InputValue::Axis(0.0)),
+            InputValue::Analog { .. } => Some(InputValue::Analog { x: 0.0, y: 0.0 }),
+            InputValue::PointerWheel(_) => Some(InputValue::PointerWheel(0.0)),
+            _ => None,
+        }
+    }
+
+    fn magnitude(&self) -> f32 {
+        match *self {
+            InputValue::Digital(_) => self.axis(),
+            InputValue::Axis(v) | InputValue::PointerWheel(v) => v.abs(),
+            InputValue::Analog { x, y } | InputValue::PointerPosition { x, y } => {
+                (x * x + y * y).sqrt()
+            }
+            InputValue::PointerInWindow(_) => 0.0,
+        }
+    }
+}
+
+//Anything that can name an action, like an enum of the game's controls or a String
+pub trait ActionKey: Hash + Eq + Clone {}
+
+impl<T> ActionKey for T where T: Hash + Eq + Clone {}
+
+//Removed users and actions leave their slot to the next one added, so handles remember
+//the generation of the slot they were made for and go stale once it is reused
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct UserHandle {
+    index: usize,
+    generation: u32,
+}
+
+impl UserHandle {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+//An action of one user, the actions of other users reject it
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ActionHandle {
+    user: UserHandle,
+    index: usize,
+    generation: u32,
+}
+
+impl ActionHandle {
+    pub fn user(&self) -> UserHandle {
+        self.user
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+//Devices write raw states whenever their events come in, while actions only change in advance.
+//Every press and release is queued, so each one is seen by exactly one update.
+pub struct InputActions<A = String> {
+    user: UserHandle,
+    actions: Vec<ActionType>,
+    action_map: HashMap<A, usize>,
+    generations: Vec<u32>,
+    //Slots of removed actions
+    free: Vec<usize>,
+
+    raw: Vec<ActionType>,
+    edges: Vec<VecDeque<bool>>,
+
+    timings: Vec<Timing>,
+    gestures: GestureConfig,
+    tick: u64,
+
+    sequences: SequenceBuffer,
+}
+
+impl<A> InputActions<A> {
+    pub fn new() -> Self {
+        Self::for_user(0)
+    }
+
+    //Handles made by these actions carry the user
+    pub fn for_user(user: usize) -> Self {
+        Self::for_user_handle(UserHandle {
+            index: user,
+            generation: 0,
+        })
+    }
+
+    fn for_user_handle(user: UserHandle) -> Self {
+        InputActions {
+            user,
+            actions: Vec::new(),
+            action_map: HashMap::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+
+            raw: Vec::new(),
+            edges: Vec::new(),
+
+            timings: Vec::new(),
+            gestures: GestureConfig::default(),
+            tick: 0,
+
+            sequences: SequenceBuffer::new(),
+        }
+    }
+
+    pub fn update_action(
+        &mut self,
+        handle: ActionHandle,
+        new: ActionType,
+    ) -> Result<(), InputError> {
+        let index = self.check_handle(handle)?;
+        self.update_index(index, new);
+
+        Ok(())
+    }
+
+    fn update_index(&mut self, index: usize, new: ActionType) {
+        self.raw[index] = match (self.raw[index], new) {
+            (ActionType::Digital(old), ActionType::Digital(new)) => {
+                if old.is_down() != new.is_down() {
+                    self.edges[index].push_back(new.is_down());
+                }
+
+                ActionType::Digital(match new.is_down() {
+                    true => ActionState::Down,
+                    false => ActionState::Up,
+                })
+            }
+            (old, new) => ActionType::update(old, new),
+        };
+    }
+
+    //Call once per update, before scenes read their actions.
+    //Takes at most one queued edge per digital action, later ones wait for the next update.
+    pub fn advance(&mut self, delta: f64) {
+        self.tick += 1;
+
+        for i in 0..self.actions.len() {
+            self.actions[i] = match self.raw[i] {
+                ActionType::Digital(_) => {
+                    let down = self.edges[i]
+                        .pop_front()
+                        .unwrap_or(self.actions[i].is_down());
+
+                    ActionType::update(
+                        self.actions[i],
+                        ActionType::Digital(match down {
+                            true => ActionState::Down,
+                            false => ActionState::Up,
+                        }),
+                    )
+                }
+                //Pointer movement and wheel only last for the update that saw them
+                ActionType::Pointer(pointer) => {
+                    self.raw[i] = ActionType::Pointer(pointer.consumed());
+                    ActionType::Pointer(pointer)
+                }
+                raw => raw,
+            };
+        }
+
+        let held: Vec<usize> = (0..self.actions.len())
+            .filter(|i| self.actions[*i].is_down())
+            .collect();
+        let mut edges = Vec::new();
+
+        for (i, (action, timing)) in self.actions.iter().zip(self.timings.iter_mut()).enumerate() {
+            if action.is_down() != timing.was_down() {
+                edges.push(SequenceEdge {
+                    tick: self.tick,
+                    action: i,
+                    pressed: action.is_down(),
+                    held: held.clone(),
+                });
+            }
+
+            timing.advance(action.is_down(), delta);
+        }
+
+        self.sequences.record(self.tick, edges);
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    //Used by actions added from now on and replaces the config of existing ones
+    pub fn set_gestures(&mut self, config: GestureConfig) {
+        self.gestures = config;
+
+        for timing in self.timings.iter_mut() {
+            timing.config = config;
+        }
+    }
+
+    pub fn set_action_gestures(
+        &mut self,
+        handle: ActionHandle,
+        config: GestureConfig,
+    ) -> Result<(), InputError> {
+        self.timing_mut(handle)?.config = config;
+
+        Ok(())
+    }
+
+    //Seconds since the action was pressed, zero while it is up
+    pub fn held_for(&self, handle: ActionHandle) -> Result<f64, InputError> {
+        Ok(self.timing(handle)?.held)
+    }
+
+    pub fn double_tapped(&self, handle: ActionHandle) -> Result<bool, InputError> {
+        Ok(self.timing(handle)?.double_tapped)
+    }
+
+    //Fires once per press, when held past the long press time
+    pub fn long_pressed(&self, handle: ActionHandle) -> Result<bool, InputError> {
+        Ok(self.timing(handle)?.long_pressed)
+    }
+
+    //True on the press, then again at the repeat rate after the repeat delay
+    pub fn repeated(&self, handle: ActionHandle) -> Result<bool, InputError> {
+        Ok(self.timing(handle)?.repeated)
+    }
+
+    //Every step has to be an action of this user
+    pub fn add_sequence(&mut self, sequence: Sequence) -> Result<usize, InputError> {
+        for handle in sequence.steps.iter().flatten() {
+            self.check_handle(*handle)?;
+        }
+
+        Ok(self.sequences.add(sequence))
+    }
+
+    //True only for the update the sequence was completed in
+    pub fn sequence_matched(&self, index: usize) -> Result<bool, InputError> {
+        if index >= self.sequences.len() {
+            return Err(InputError::SequenceIndexOutOfBounds);
+        }
+
+        Ok(self.sequences.matched().contains(&index))
+    }
+
+    pub fn matched_sequences(&self) -> &[usize] {
+        self.sequences.matched()
+    }
+
+    //Recent presses and releases, oldest first
+    pub fn sequence_edges(&self) -> impl Iterator<Item = &SequenceEdge> {
+        self.sequences.edges().iter()
+    }
+
+    fn timing(&self, handle: ActionHandle) -> Result<&Timing, InputError> {
+        Ok(&self.timings[self.check_handle(handle)?])
+    }
+
+    fn timing_mut(&mut self, handle: ActionHandle) -> Result<&mut Timing, InputError> {
+        let index = self.check_handle(handle)?;
+
+        Ok(&mut self.timings[index])
+    }
+
+    pub fn user(&self) -> UserHandle {
+        self.user
+    }
+
+    pub fn get_action_by_handle(&self, handle: ActionHandle) -> Result<ActionType, InputError> {
+        Ok(self.actions[self.check_handle(handle)?])
+    }
+
+    //The latest state written by devices, which scenes see after the next advance
+    pub fn get_raw_action_by_handle(&self, handle: ActionHandle) -> Result<ActionType, InputError> {
+        Ok(self.raw[self.check_handle(handle)?])
+    }
+
+    pub fn get_key_by_handle(&self, handle: ActionHandle) -> Result<&A, InputError> {
+        self.key_by_index(self.check_handle(handle)?)
+    }
+
+    //Every action the user has, in slot order
+    pub fn handles(&self) -> Vec<ActionHandle> {
+        (0..self.actions.len())
+            .filter(|i| self.contains(*i))
+            .map(|i| self.handle(i))
+            .collect()
+    }
+
+    fn key_by_index(&self, index: usize) -> Result<&A, InputError> {
+        self.action_map
+            .iter()
+            .find(|(_, i)| **i == index)
+            .map(|(k, _)| k)
+            .ok_or(InputError::ActionIndexOutOfBounds)
+    }
+
+    pub fn check_handle(&self, handle: ActionHandle) -> Result<usize, InputError> {
+        if handle.user.index != self.user.index {
+            return Err(InputError::HandleUserMismatch);
+        }
+
+        if handle.user.generation != self.user.generation
+            || self.generations.get(handle.index) != Some(&handle.generation)
+            || self.free.contains(&handle.index)
+        {
+            return Err(InputError::StaleHandle);
+        }
+
+        Ok(handle.index)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.actions.len() && !self.free.contains(&index)
+    }
+
+    //Counts the slots of removed actions too, so every index is below it
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.len() == self.free.len()
+    }
+
+    fn handle(&self, index: usize) -> ActionHandle {
+        ActionHandle {
+            user: self.user,
+            index,
+            generation: self.generations[index],
+        }
+    }
+
+    //The slot keeps a released state until an action reuses it
+    pub(super) fn remove_action(&mut self, handle: ActionHandle) -> Result<(), InputError> {
+        let index = self.check_handle(handle)?;
+
+        self.action_map.retain(|_, i| *i != index);
+        self.generations[index] += 1;
+        self.free.push(index);
+
+        self.actions[index] = ActionType::Digital(ActionState::Up);
+        self.raw[index] = ActionType::Digital(ActionState::Up);
+        self.edges[index].clear();
+        self.timings[index] = Timing::new(self.gestures);
+        self.sequences.remove_action(index);
+
+        Ok(())
+    }
+}
+
+impl<A> InputActions<A>
+where
+    A: ActionKey,
+{
+    pub fn add_action(&mut self, key: A, action: ActionType) -> ActionHandle {
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.actions[index] = action;
+                self.raw[index] = action;
+                index
+            }
+            None => {
+                self.actions.push(action);
+                self.raw.push(action);
+                self.edges.push(VecDeque::new());
+                self.timings.push(Timing::new(self.gestures));
+                self.generations.push(0);
+
+                self.actions.len() - 1
+            }
+        };
+
+        self.action_map.insert(key, index);
+
+        self.handle(index)
+    }
+
+    //Takes &str for String keys
+    pub fn get_index_by_key<Q>(&self, key: &Q) -> Result<usize, InputError>
+    where
+        A: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.action_map.get(key) {
+            Some(i) => Ok(*i),
+            None => Err(InputError::UnrecognizedAction),
+        }
+    }
+
+    pub fn get_handle_by_key<Q>(&self, key: &Q) -> Result<ActionHandle, InputError>
+    where
+        A: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Ok(self.handle(self.get_index_by_key(key)?))
+    }
+
+    pub fn get_action_by_key<Q>(&self, key: &Q) -> Result<ActionType, InputError>
+    where
+        A: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Ok(self.actions[self.get_index_by_key(key)?])
+    }
+}
+
+//Any of the bindings can drive the action while its context is active
+#[derive(Clone)]
+pub struct InputCommand<C> {
+    pub user_index: usize,
+    pub action_index: usize,
+    pub context: usize,
+    pub bindings: Vec<Binding<C>>,
+}
+
+pub struct InputMap<C, A = String> {
+    //Removed users leave an empty slot behind
+    pub users: Vec<InputActions<A>>,
+    free_users: Vec<usize>,
+    pub text: TextInput,
+    commands: Vec<InputCommand<C>>,
+
+    //Raw command to the input commands that mention it
+    index: HashMap<C, Vec<usize>>,
+    //Inputs without a device, which every user shares
+    held: Held<C>,
+    //By user, the shared inputs plus the ones from the user's devices
+    user_held: Vec<Held<C>>,
+
+    modifiers: HashSet<C>,
+    exclusive: Vec<usize>,
+
+    defaults: Vec<InputCommand<C>>,
+    capture: Option<Capture<C>>,
+
+    //By user and action, then by the input the response shapes
+    responses: HashMap<(usize, usize), HashMap<C, AxisResponse>>,
+
+    contexts: Vec<InputContext>,
+    stack: Vec<usize>,
+
+    devices: Devices<C>,
+    touch: Touches<C>,
+    haptics: Haptics,
+    recorder: Recorder<C>,
+}
+
+enum Capture<C> {
+    Waiting,
+    Modifier(C),
+    Done(Binding<C>),
+}
+
+impl<C, A> InputMap<C, A> {
+    pub fn new() -> Self {
+        InputMap {
+            users: Vec::new(),
+            free_users: Vec::new(),
+            text: TextInput::new(),
+            commands: Vec::new(),
+            index: HashMap::new(),
+            held: Held::new(),
+            user_held: Vec::new(),
+            modifiers: HashSet::new(),
+            exclusive: Vec::new(),
+            defaults: Vec::new(),
+            capture: None,
+            responses: HashMap::new(),
+
+            contexts: vec![InputContext {
+                name: DEFAULT_CONTEXT.to_string(),
+                pass_through: false,
+            }],
+            stack: vec![0],
+
+            devices: Devices::new(),
+            touch: Touches::new(),
+            haptics: Haptics::new(),
+            recorder: Recorder::new(),
+        }
+    }
+
+    pub fn has_user(&self, user: UserHandle) -> bool {
+        self.check_user(user).is_ok()
+    }
+
+    //The handle of the user in a slot, like the ones saved in a profile
+    pub fn user_handle(&self, user: usize) -> Result<UserHandle, InputError> {
+        match self.is_slot_used(user) {
+            true => Ok(self.users[user].user()),
+            false => Err(InputError::UserIndexOutOfBounds),
+        }
+    }
+
+    //The slot of a user that was not removed since the handle was made
+    pub fn check_user(&self, user: UserHandle) -> Result<usize, InputError> {
+        if user.index >= self.users.len() {
+            return Err(InputError::UserIndexOutOfBounds);
+        }
+
+        match self.is_slot_used(user.index) && self.users[user.index].user() == user {
+            true => Ok(user.index),
+            false => Err(InputError::StaleHandle),
+        }
+    }
+
+    pub fn user(&self, user: UserHandle) -> Result<&InputActions<A>, InputError> {
+        Ok(&self.users[self.check_user(user)?])
+    }
+
+    pub fn user_mut(&mut self, user: UserHandle) -> Result<&mut InputActions<A>, InputError> {
+        let user = self.check_user(user)?;
+
+        Ok(&mut self.users[user])
+    }
+
+    fn is_slot_used(&self, user: usize) -> bool {
+        user < self.users.len() && !self.free_users.contains(&user)
+    }
+
+    pub fn get_action_by_handle(&self, handle: ActionHandle) -> Result<ActionType, InputError> {
+        self.users
+            .get(handle.user.index)
+            .ok_or(InputError::UserIndexOutOfBounds)?
+            .get_action_by_handle(handle)
+    }
+
+    pub fn commands(&self) -> &[InputCommand<C>] {
+        &self.commands
+    }
+}
+
+impl<C, A> Default for InputMap<C, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+{
+    pub fn advance(&mut self, delta: f64) {
+        self.advance_recorder();
+
+        for user in self.users.iter_mut() {
+            user.advance(delta);
+        }
+
+        self.settle_wheels();
+        self.text.advance();
+        self.touch.advance(delta);
+        self.haptics.advance(delta);
+    }
+
+    //The wheel goes back to zero once an update has seen it turn
+    fn settle_wheels(&mut self) {
+        let settle = InputValue::PointerWheel(0.0);
+        self.devices.settle_wheels();
+
+        for input in self.held.turned_wheels() {
+            if let Err(e) = self.apply_input(None, &input, settle) {
+                warn!("Failed to settle the wheel: {:?}", e);
+            }
+        }
+
+        for user in 0..self.user_held.len() {
+            for input in self.user_held[user].turned_wheels() {
+                if let Err(e) = self.apply_input(Some(user), &input, settle) {
+                    warn!("Failed to settle the wheel: {:?}", e);
+                }
+            }
+        }
+    }
+
+    //Reuses the slot of a removed user, which was already emptied by remove_user
+    pub fn add_user(&mut self) -> UserHandle {
+        if let Some(user) = self.free_users.pop() {
+            return self.users[user].user();
+        }
+
+        self.users.push(InputActions::for_user(self.users.len()));
+        self.user_held.push(self.held.clone());
+        self.haptics.add_user();
+
+        self.users[self.users.len() - 1].user()
+    }
+
+    //Takes plain commands, chords or a mix of both as bindings, in the default context
+    pub fn add_action<B>(
+        &mut self,
+        user: UserHandle,
+        key: A,
+        commands: Vec<B>,
+        action: ActionType,
+    ) -> Result<ActionHandle, InputError>
+    where
+        A: ActionKey,
+        B: Into<Binding<C>>,
+    {
+        self.add_bound_action(0, user, key, commands, action)
+    }
+
+    fn add_bound_action<B>(
+        &mut self,
+        context: usize,
+        user: UserHandle,
+        key: A,
+        commands: Vec<B>,
+        action: ActionType,
+    ) -> Result<ActionHandle, InputError>
+    where
+        A: ActionKey,
+        B: Into<Binding<C>>,
+    {
+        let user = self.check_user(user)?;
+        let handle = self.users[user].add_action(key, action);
+
+        let command = InputCommand {
+            user_index: user,
+            action_index: handle.index,
+            context,
+            bindings: commands.into_iter().map(|b| b.into()).collect(),
+        };
+        self.defaults.push(command.clone());
+        self.commands.push(command);
+
+        self.rebuild_index();
+
+        Ok(handle)
+    }
+
+    pub fn bindings(&self, handle: ActionHandle) -> Result<Vec<&Binding<C>>, InputError> {
+        let (user, action) = self.check_action(handle)?;
+
+        Ok(self
+            .commands
+            .iter()
+            .filter(|c| c.user_index == user && c.action_index == action)
+            .flat_map(|c| c.bindings.iter())
+            .collect())
+    }
+
+    pub fn set_bindings<B>(
+        &mut self,
+        handle: ActionHandle,
+        bindings: Vec<B>,
+    ) -> Result<(), InputError>
+    where
+        B: Into<Binding<C>>,
+    {
+        let (user, action) = self.check_action(handle)?;
+
+        let context = self
+            .commands
+            .iter()
+            .find(|c| c.user_index == user && c.action_index == action)
+            .map_or(0, |c| c.context);
+
+        self.commands
+            .retain(|c| c.user_index != user || c.action_index != action);
+        self.commands.push(InputCommand {
+            user_index: user,
+            action_index: action,
+            context,
+            bindings: bindings.into_iter().map(|b| b.into()).collect(),
+        });
+
+        self.rebuild_index();
+        self.refresh(self.commands.len() - 1)
+    }
+
+    pub fn append_binding<B>(&mut self, handle: ActionHandle, binding: B) -> Result<(), InputError>
+    where
+        B: Into<Binding<C>>,
+    {
+        let mut bindings: Vec<Binding<C>> = self.bindings(handle)?.into_iter().cloned().collect();
+        bindings.push(binding.into());
+
+        self.set_bindings(handle, bindings)
+    }
+
+    pub fn remove_binding(
+        &mut self,
+        handle: ActionHandle,
+        binding: &Binding<C>,
+    ) -> Result<(), InputError> {
+        let bindings: Vec<Binding<C>> = self
+            .bindings(handle)?
+            .into_iter()
+            .filter(|b| *b != binding)
+            .cloned()
+            .collect();
+
+        self.set_bindings(handle, bindings)
+    }
+
+    //Drops the action's bindings and responses, its handles go stale
+    pub fn remove_action(&mut self, handle: ActionHandle) -> Result<(), InputError> {
+        let (user, action) = (handle.user.index, handle.index);
+
+        self.users
+            .get_mut(user)
+            .ok_or(InputError::UserIndexOutOfBounds)?
+            .remove_action(handle)?;
+
+        self.commands
+            .retain(|c| c.user_index != user || c.action_index != action);
+        self.defaults
+            .retain(|c| c.user_index != user || c.action_index != action);
+        self.responses.remove(&(user, action));
+
+        self.rebuild_index();
+
+        Ok(())
+    }
+
+    //Also lets go of the user's devices and rumble. Handles of the user and its actions go stale.
+    pub fn remove_user(&mut self, handle: UserHandle) -> Result<(), InputError> {
+        let user = self.check_user(handle)?;
+
+        self.commands.retain(|c| c.user_index != user);
+        self.defaults.retain(|c| c.user_index != user);
+        self.responses.retain(|(u, _), _| *u != user);
+        self.rebuild_index();
+
+        self.devices.forget_user(user);
+        self.haptics.clear(user);
+        //Shared inputs like the keyboard are still held for whoever takes the slot next
+        self.user_held[user] = self.held.clone();
+
+        self.users[user] = InputActions::for_user_handle(UserHandle {
+            index: user,
+            generation: handle.generation + 1,
+        });
+        self.free_users.push(user);
+
+        Ok(())
+    }
+
+    //Applies to the raw values of the input whenever it drives the action
+    pub fn set_response(
+        &mut self,
+        handle: ActionHandle,
+        input: C,
+        response: AxisResponse,
+    ) -> Result<(), InputError> {
+        let (user, action) = self.check_action(handle)?;
+
+        self.responses
+            .entry((user, action))
+            .or_default()
+            .insert(input, response);
+
+        Ok(())
+    }
+
+    pub fn clear_response(&mut self, handle: ActionHandle, input: &C) -> Result<(), InputError> {
+        let key = self.check_action(handle)?;
+
+        if let Some(responses) = self.responses.get_mut(&key) {
+            responses.remove(input);
+        }
+
+        Ok(())
+    }
+
+    //Restores the bindings the actions were added with
+    pub fn reset_to_defaults(&mut self) -> Result<(), InputError> {
+        self.commands = self.defaults.clone();
+        self.rebuild_index();
+
+        for i in 0..self.commands.len() {
+            self.refresh(i)?;
+        }
+
+        Ok(())
+    }
+
+    //The next input pressed is swallowed and kept for take_captured,
+    //held modifiers turn it into a chord
+    pub fn capture_next(&mut self) {
+        self.capture = Some(Capture::Waiting);
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capture = None;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        matches!(
+            self.capture,
+            Some(Capture::Waiting) | Some(Capture::Modifier(_))
+        )
+    }
+
+    pub fn take_captured(&mut self) -> Option<Binding<C>> {
+        match self.capture.take() {
+            Some(Capture::Done(binding)) => Some(binding),
+            other => {
+                self.capture = other;
+                None
+            }
+        }
+    }
+
+    //The user and action index of a handle that is still current
+    fn check_action(&self, handle: ActionHandle) -> Result<(usize, usize), InputError> {
+        let user = self.check_user(handle.user)?;
+
+        Ok((user, self.users[user].check_handle(handle)?))
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index.clear();
+        self.modifiers.clear();
+        self.exclusive.clear();
+
+        for (position, command) in self.commands.iter().enumerate() {
+            for binding in command.bindings.iter() {
+                for c in binding.inputs() {
+                    let commands = self.index.entry(c.clone()).or_default();
+                    if !commands.contains(&position) {
+                        commands.push(position);
+                    }
+                }
+
+                self.modifiers.extend(binding.modifiers().iter().cloned());
+            }
+
+            if command.bindings.iter().any(|b| b.is_exclusive()) {
+                self.exclusive.push(position);
+            }
+        }
+    }
+
+    fn capture(&mut self, source: Option<usize>, command: &C, value: InputValue) {
+        let was_down = self.source_held(source).is_down(command);
+
+        self.capture = match self.capture.take() {
+            Some(Capture::Waiting) if value.is_down() && !was_down => {
+                match self.modifiers.contains(command) {
+                    true => Some(Capture::Modifier(command.clone())),
+                    false => Some(Capture::Done(self.captured_binding(source, command))),
+                }
+            }
+            Some(Capture::Modifier(m)) if value.is_down() && !was_down => {
+                match self.modifiers.contains(command) {
+                    true => Some(Capture::Modifier(m)),
+                    false => Some(Capture::Done(self.captured_binding(source, command))),
+                }
+            }
+            //A modifier pressed and released alone is bound by itself
+            Some(Capture::Modifier(m)) if m == *command && !value.is_down() => {
+                Some(Capture::Done(Binding::Single(m)))
+            }
+            other => other,
+        };
+    }
+
+    fn captured_binding(&self, source: Option<usize>, key: &C) -> Binding<C> {
+        let held = self.source_held(source);
+        let modifiers: Vec<C> = self
+            .modifiers
+            .iter()
+            .filter(|m| held.is_down(m))
+            .cloned()
+            .collect();
+
+        match modifiers.is_empty() {
+            true => Binding::Single(key.clone()),
+            false => Binding::Chord(Chord::new(modifiers, key.clone())),
+        }
+    }
+
+    fn source_held(&self, source: Option<usize>) -> &Held<C> {
+        match source {
+            Some(user) => &self.user_held[user],
+            None => &self.held,
+        }
+    }
+
+    //Updates every action bound to the command from the raw value of a shared device,
+    //like the keyboard. Claimed devices go through process_from.
+    pub fn process(&mut self, command: &C, value: InputValue) -> Result<(), InputError> {
+        self.live(None, command, value)?;
+
+        self.apply_input(None, command, value)
+    }
+
+    //Only the user from source sees the input, every user sees it without one
+    pub(super) fn apply_input(
+        &mut self,
+        source: Option<usize>,
+        command: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        let capturing = self.is_capturing();
+        if capturing {
+            self.capture(source, command, value);
+        }
+
+        match source {
+            Some(user) => self.user_held[user].insert(command, value),
+            None => {
+                self.held.insert(command, value);
+
+                for held in self.user_held.iter_mut() {
+                    held.insert(command, value);
+                }
+            }
+        }
+
+        //Presses are swallowed while capturing, but releases still go through
+        //so actions held when the capture started let go
+        if capturing && value.is_down() {
+            return Ok(());
+        }
+
+        for i in self.affected(command) {
+            self.refresh(i)?;
+        }
+
+        if !capturing
+            && matches!(
+                value,
+                InputValue::PointerPosition { .. }
+                    | InputValue::PointerWheel(_)
+                    | InputValue::PointerInWindow(_)
+            )
+        {
+            self.point(source, command, value)?;
+        }
+
+        Ok(())
+    }
+
+    //Pointer actions accumulate events instead of taking the latest value
+    fn point(
+        &mut self,
+        source: Option<usize>,
+        command: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        let bound: Vec<usize> = match self.index.get(command) {
+            Some(bound) => bound
+                .iter()
+                .copied()
+                .filter(|i| source.is_none_or(|user| self.commands[*i].user_index == user))
+                .filter(|i| {
+                    self.commands[*i].bindings.iter().any(|b| {
+                        b.key() == Some(command)
+                            && self
+                                .live_held(*i, b)
+                                .is_some_and(|held| b.value(&held, &self.modifiers).is_some())
+                    })
+                })
+                .collect(),
+            None => return Ok(()),
+        };
+
+        for i in bound {
+            let binding = &self.commands[i];
+            let user = &mut self.users[binding.user_index];
+
+            if let ActionType::Pointer(pointer) = user.raw[binding.action_index] {
+                user.update_index(
+                    binding.action_index,
+                    ActionType::Pointer(pointer.apply(value)),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    //Commands bound to the input, plus the ones a chord using it might shadow
+    fn affected(&self, command: &C) -> Vec<usize> {
+        let mut affected = Vec::new();
+
+        if let Some(direct) = self.index.get(command) {
+            for i in direct.iter() {
+                for binding in self.commands[*i].bindings.iter() {
+                    if !binding.inputs().contains(&command) {
+                        continue;
+                    }
+
+                    for input in binding.inputs() {
+                        if let Some(near) = self.index.get(input) {
+                            affected.extend(near.iter());
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.modifiers.contains(command) {
+            affected.extend(self.exclusive.iter());
+        }
+
+        affected.sort_unstable();
+        affected.dedup();
+
+        affected
+    }
+
+    fn refresh(&mut self, index: usize) -> Result<(), InputError> {
+        let command = &self.commands[index];
+        let responses = self
+            .responses
+            .get(&(command.user_index, command.action_index));
+
+        let values: Vec<InputValue> = command
+            .bindings
+            .iter()
+            .filter(|b| !self.shadowed(index, b))
+            .filter_map(|b| {
+                let held = self.live_held(index, b)?;
+                let value = b.value(&held, &self.modifiers)?;
+
+                match responses.and_then(|r| r.get(b.key()?)) {
+                    Some(response) => Some(response.apply(value)),
+                    None => Some(value),
+                }
+            })
+            .collect();
+
+        let user = &mut self.users[command.user_index];
+        let current = user.raw[command.action_index];
+        user.update_index(command.action_index, Self::resolve(&values, current));
+
+        Ok(())
+    }
+
+    //A held binding hides every less specific binding of the same user,
+    //so holding Shift+Up does not also trigger Up
+    fn shadowed(&self, index: usize, binding: &Binding<C>) -> bool {
+        let user = self.commands[index].user_index;
+
+        let key = match binding.key() {
+            Some(key) => key,
+            None => return false,
+        };
+
+        self.index.get(key).is_some_and(|candidates| {
+            candidates
+                .iter()
+                .filter(|i| **i != index && self.commands[**i].user_index == user)
+                .any(|i| {
+                    self.commands[*i].bindings.iter().any(|other| {
+                        other.is_more_specific(binding)
+                            && self
+                                .live_held(*i, other)
+                                .is_some_and(|held| other.is_active(&held, &self.modifiers))
+                    })
+                })
+        })
+    }
+
+    //The strongest value wins
+    fn resolve(values: &[InputValue], current: ActionType) -> ActionType {
+        let strongest =
+            values
+                .iter()
+                .fold(None, |strongest: Option<&InputValue>, v| match strongest {
+                    Some(s) if s.magnitude() >= v.magnitude() => Some(s),
+                    _ => Some(v),
+                });
+
+        match current {
+            ActionType::Digital(_) => {
+                ActionType::Digital(match values.iter().any(|v| v.is_down()) {
+                    true => ActionState::Down,
+                    false => ActionState::Up,
+                })
+            }
+            ActionType::Axis(_) => ActionType::Axis(strongest.map_or(0.0, |v| v.axis())),
+            ActionType::Analog { .. } => {
+                let (x, y) = strongest.map_or((0.0, 0.0), |v| v.analog());
+
+                ActionType::Analog { x, y }
+            }
+            ActionType::Pointer(_) => current,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    ActionIndexOutOfBounds,
+    DeviceAlreadyClaimed,
+    HandleUserMismatch,
+    SequenceIndexOutOfBounds,
+    StaleHandle,
+    UnknownContext,
+    UnrecognizedAction,
+    UserIndexOutOfBounds,
+}
@@ -0,0 +1,132 @@
+use super::InputValue;
+
+//Absolute position in window pixels, with the movement and wheel since the last update
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pointer {
+    pub x: f32,
+    pub y: f32,
+    pub dx: f32,
+    pub dy: f32,
+    pub wheel: f32,
+    pub in_window: bool,
+
+    //The first position has nothing to move from
+    seen: bool,
+}
+
+impl Pointer {
+    pub fn moved(&self) -> bool {
+        self.dx != 0.0 || self.dy != 0.0
+    }
+
+    pub(super) fn apply(mut self, value: InputValue) -> Pointer {
+        match value {
+            InputValue::PointerPosition { x, y } => {
+                if self.seen {
+                    self.dx += x - self.x;
+                    self.dy += y - self.y;
+                }
+                self.x = x;
+                self.y = y;
+                self.seen = true;
+            }
+            InputValue::PointerWheel(wheel) => self.wheel += wheel,
+            InputValue::PointerInWindow(in_window) => self.in_window = in_window,
+            _ => {}
+        }
+
+        self
+    }
+
+    pub(super) fn consumed(mut self) -> Pointer {
+        self.dx = 0.0;
+        self.dy = 0.0;
+        self.wheel = 0.0;
+
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScaleMode {
+    //Fills the window, distorting the aspect ratio
+    Stretch,
+    //Keeps the aspect ratio, with bars on the sides that are left over
+    Letterbox,
+}
+
+//Maps window pixels onto the resolution the game draws at
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub window: (f32, f32),
+    pub resolution: (f32, f32),
+    pub mode: ScaleMode,
+}
+
+impl Viewport {
+    pub fn new(window: (f32, f32), resolution: (f32, f32), mode: ScaleMode) -> Self {
+        Viewport {
+            window,
+            resolution,
+            mode,
+        }
+    }
+
+    pub fn to_virtual(&self, x: f32, y: f32) -> (f32, f32) {
+        let (scale, offset) = self.transform();
+
+        ((x - offset.0) / scale.0, (y - offset.1) / scale.1)
+    }
+
+    //Letterbox bars are outside of the virtual screen
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (x, y) = self.to_virtual(x, y);
+
+        x >= 0.0 && y >= 0.0 && x <= self.resolution.0 && y <= self.resolution.1
+    }
+
+    pub fn pointer_to_virtual(&self, pointer: Pointer) -> Pointer {
+        let (scale, _) = self.transform();
+        let (x, y) = self.to_virtual(pointer.x, pointer.y);
+
+        Pointer {
+            x,
+            y,
+            dx: pointer.dx / scale.0,
+            dy: pointer.dy / scale.1,
+            wheel: pointer.wheel,
+            in_window: pointer.in_window && self.contains(pointer.x, pointer.y),
+            seen: pointer.seen,
+        }
+    }
+
+    //From the virtual screen into the world seen by a camera centered on center
+    pub fn to_camera(&self, x: f32, y: f32, center: (f32, f32), zoom: f32) -> (f32, f32) {
+        (
+            (x - self.resolution.0 * 0.5) / zoom + center.0,
+            (y - self.resolution.1 * 0.5) / zoom + center.1,
+        )
+    }
+
+    fn transform(&self) -> ((f32, f32), (f32, f32)) {
+        let scale = (
+            self.window.0 / self.resolution.0,
+            self.window.1 / self.resolution.1,
+        );
+
+        match self.mode {
+            ScaleMode::Stretch => (scale, (0.0, 0.0)),
+            ScaleMode::Letterbox => {
+                let uniform = scale.0.min(scale.1);
+
+                (
+                    (uniform, uniform),
+                    (
+                        (self.window.0 - self.resolution.0 * uniform) * 0.5,
+                        (self.window.1 - self.resolution.1 * uniform) * 0.5,
+                    ),
+                )
+            }
+        }
+    }
+}
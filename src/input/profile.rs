@@ -0,0 +1,183 @@
+use log::warn;
+use std::{fmt, fs, hash::Hash, io, path::Path, str::FromStr};
+
+use super::{binding::Binding, ActionKey, InputError, InputMap};
+
+const HEADER: &str = "stagehand-bindings";
+
+//Every user's bindings by action key, saved as text like
+//
+//stagehand-bindings 1
+//
+//[user 0]
+//Forward = W, Shift+Up, LMB
+pub struct BindingProfile<C> {
+    pub version: u32,
+    pub users: Vec<Vec<(String, Vec<Binding<C>>)>>,
+}
+
+impl<C: fmt::Display> fmt::Display for BindingProfile<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, self.version)?;
+
+        for (i, user) in self.users.iter().enumerate() {
+            writeln!(f)?;
+            writeln!(f, "[user {}]", i)?;
+
+            for (key, bindings) in user.iter() {
+                let bindings: Vec<String> = bindings.iter().map(|b| b.to_string()).collect();
+                writeln!(f, "{} = {}", key, bindings.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: FromStr> BindingProfile<C> {
+    pub fn parse(text: &str) -> Result<Self, ProfileError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'));
+
+        let version = match lines.next() {
+            Some((line, l)) => l
+                .strip_prefix(HEADER)
+                .and_then(|v| v.trim().parse().ok())
+                .ok_or_else(|| ProfileError::Parse(line, l.to_string()))?,
+            None => return Err(ProfileError::Parse(0, String::new())),
+        };
+
+        let mut users: Vec<Vec<(String, Vec<Binding<C>>)>> = Vec::new();
+        for (line, l) in lines {
+            let error = || ProfileError::Parse(line, l.to_string());
+
+            if let Some(user) = l.strip_prefix("[user ").and_then(|u| u.strip_suffix(']')) {
+                if user.trim().parse::<usize>().ok() != Some(users.len()) {
+                    return Err(error());
+                }
+
+                users.push(Vec::new());
+                continue;
+            }
+
+            let (key, bindings) = l.split_once('=').ok_or_else(error)?;
+            let bindings = bindings
+                .split(',')
+                .map(|b| b.trim())
+                .filter(|b| !b.is_empty())
+                .map(|b| b.parse().map_err(|_| error()))
+                .collect::<Result<Vec<Binding<C>>, ProfileError>>()?;
+
+            users
+                .last_mut()
+                .ok_or_else(error)?
+                .push((key.trim().to_string(), bindings));
+        }
+
+        Ok(BindingProfile { version, users })
+    }
+
+    //Fails if the file was written for another version of the bindings
+    pub fn load<P: AsRef<Path>>(path: P, version: u32) -> Result<Self, ProfileError> {
+        let profile = Self::parse(&fs::read_to_string(path).map_err(ProfileError::Io)?)?;
+
+        if profile.version != version {
+            return Err(ProfileError::Version {
+                expected: version,
+                found: profile.version,
+            });
+        }
+
+        Ok(profile)
+    }
+}
+
+impl<C: fmt::Display> BindingProfile<C> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ProfileError> {
+        fs::write(path, self.to_string()).map_err(ProfileError::Io)
+    }
+}
+
+//Action keys are saved by their text, so enum keys only need a Display impl
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+    A: ActionKey + fmt::Display,
+{
+    pub fn profile(&self, version: u32) -> BindingProfile<C> {
+        let users = self
+            .users
+            .iter()
+            .map(|actions| {
+                actions
+                    .handles()
+                    .into_iter()
+                    .filter_map(|handle| {
+                        let key = actions.get_key_by_handle(handle).ok()?;
+                        let bindings = self.bindings(handle).ok()?;
+
+                        Some((key.to_string(), bindings.into_iter().cloned().collect()))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        BindingProfile { version, users }
+    }
+
+    //Actions missing from the map are skipped, so old profiles still load
+    pub fn apply_profile(&mut self, profile: &BindingProfile<C>) -> Result<(), ProfileError> {
+        for (user, actions) in profile.users.iter().enumerate() {
+            if user >= self.users.len() {
+                return Err(ProfileError::Input(InputError::UserIndexOutOfBounds));
+            }
+
+            for (key, bindings) in actions.iter() {
+                let known = &self.users[user];
+                let found = known.handles().into_iter().find(|h| {
+                    known
+                        .get_key_by_handle(*h)
+                        .is_ok_and(|k| k.to_string() == *key)
+                });
+
+                match found {
+                    Some(handle) => self
+                        .set_bindings(handle, bindings.clone())
+                        .map_err(ProfileError::Input)?,
+                    None => warn!("Skipping bindings for unknown action: {}", key),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_profile<P: AsRef<Path>>(&self, path: P, version: u32) -> Result<(), ProfileError>
+    where
+        C: fmt::Display,
+    {
+        self.profile(version).save(path)
+    }
+
+    pub fn load_profile<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        version: u32,
+    ) -> Result<(), ProfileError>
+    where
+        C: FromStr,
+    {
+        self.apply_profile(&BindingProfile::load(path, version)?)
+    }
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Input(InputError),
+    Io(io::Error),
+    Parse(usize, String),
+    Version { expected: u32, found: u32 },
+}
@@ -0,0 +1,306 @@
+use log::warn;
+use std::{collections::HashMap, fmt, fs, hash::Hash, io, path::Path, str::FromStr};
+
+use super::{device::DeviceId, InputError, InputMap, InputValue};
+
+const HEADER: &str = "stagehand-recording 1";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedInput<C> {
+    //Advances since the recording started
+    pub tick: u64,
+    pub device: Option<DeviceId>,
+    pub input: C,
+    pub value: InputValue,
+}
+
+//Raw inputs in the order they reached the map, saved as text like
+//
+//stagehand-recording 1
+//0 - d1 Space
+//12 3 s0.5,-1 LeftStick
+//
+//with the ticks since the previous input, the device or - for shared inputs,
+//the value and the input last, so it may contain spaces
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording<C> {
+    pub inputs: Vec<RecordedInput<C>>,
+}
+
+impl<C> Recording<C> {
+    pub fn new() -> Self {
+        Recording { inputs: Vec::new() }
+    }
+}
+
+impl<C> Default for Recording<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for Recording<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+
+        let mut previous = 0;
+        for input in self.inputs.iter() {
+            let device = match input.device {
+                Some(DeviceId(id)) => id.to_string(),
+                None => "-".to_string(),
+            };
+            let value = match input.value {
+                InputValue::Digital(down) => format!("d{}", down as u8),
+                InputValue::Axis(v) => format!("a{}", v),
+                InputValue::Analog { x, y } => format!("s{},{}", x, y),
+                InputValue::PointerPosition { x, y } => format!("p{},{}", x, y),
+                InputValue::PointerWheel(v) => format!("w{}", v),
+                InputValue::PointerInWindow(inside) => format!("i{}", inside as u8),
+            };
+
+            writeln!(
+                f,
+                "{} {} {} {}",
+                input.tick - previous,
+                device,
+                value,
+                input.input
+            )?;
+            previous = input.tick;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: FromStr> Recording<C> {
+    pub fn parse(text: &str) -> Result<Self, RecordingError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            Some((line, l)) => return Err(RecordingError::Parse(line, l.to_string())),
+            None => return Err(RecordingError::Parse(0, String::new())),
+        }
+
+        let mut inputs = Vec::new();
+        let mut tick = 0;
+        for (line, l) in lines {
+            let error = || RecordingError::Parse(line, l.to_string());
+
+            let mut parts = l.splitn(4, ' ');
+            let (delta, device, value, input) =
+                match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                    (Some(d), Some(s), Some(v), Some(i)) => (d, s, v, i),
+                    _ => return Err(error()),
+                };
+
+            tick += delta.parse::<u64>().map_err(|_| error())?;
+
+            inputs.push(RecordedInput {
+                tick,
+                device: match device {
+                    "-" => None,
+                    id => Some(DeviceId(id.parse().map_err(|_| error())?)),
+                },
+                input: input.parse().map_err(|_| error())?,
+                value: parse_value(value).ok_or_else(error)?,
+            });
+        }
+
+        Ok(Recording { inputs })
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, RecordingError> {
+        Self::parse(&fs::read_to_string(path).map_err(RecordingError::Io)?)
+    }
+}
+
+impl<C: fmt::Display> Recording<C> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), RecordingError> {
+        fs::write(path, self.to_string()).map_err(RecordingError::Io)
+    }
+}
+
+fn parse_value(text: &str) -> Option<InputValue> {
+    let pair = |v: &str| -> Option<(f32, f32)> {
+        let (x, y) = v.split_once(',')?;
+        Some((x.parse().ok()?, y.parse().ok()?))
+    };
+    let flag = |v: &str| match v {
+        "0" => Some(false),
+        "1" => Some(true),
+        _ => None,
+    };
+
+    let (kind, value) = text.split_at_checked(1)?;
+    match kind {
+        "d" => flag(value).map(InputValue::Digital),
+        "a" => value.parse().ok().map(InputValue::Axis),
+        "s" => pair(value).map(|(x, y)| InputValue::Analog { x, y }),
+        "p" => pair(value).map(|(x, y)| InputValue::PointerPosition { x, y }),
+        "w" => value.parse().ok().map(InputValue::PointerWheel),
+        "i" => flag(value).map(InputValue::PointerInWindow),
+        _ => None,
+    }
+}
+
+struct Playback<C> {
+    recording: Recording<C>,
+    next: usize,
+    start: u64,
+    interruptible: bool,
+    //Last value played for every input, to let go of them when playback stops
+    played: HashMap<(Option<DeviceId>, C), InputValue>,
+}
+
+pub(super) struct Recorder<C> {
+    tick: u64,
+    recording: Option<(u64, Recording<C>)>,
+    playback: Option<Playback<C>>,
+}
+
+impl<C> Recorder<C> {
+    pub fn new() -> Self {
+        Recorder {
+            tick: 0,
+            recording: None,
+            playback: None,
+        }
+    }
+}
+
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+{
+    //Played back inputs are not recorded
+    pub fn start_recording(&mut self) {
+        self.recorder.recording = Some((self.recorder.tick, Recording::new()));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Recording<C>> {
+        self.recorder.recording.take().map(|(_, r)| r)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.recording.is_some()
+    }
+
+    //Inputs are injected like they came from their devices, starting with the next advance.
+    //Interruptible playback stops as soon as anything live is pressed.
+    pub fn play(&mut self, recording: Recording<C>, interruptible: bool) -> Result<(), InputError> {
+        self.stop_playback()?;
+
+        self.recorder.playback = Some(Playback {
+            recording,
+            next: 0,
+            start: self.recorder.tick,
+            interruptible,
+            played: HashMap::new(),
+        });
+
+        Ok(())
+    }
+
+    //Lets go of everything the playback was holding
+    pub fn stop_playback(&mut self) -> Result<(), InputError> {
+        if let Some(playback) = self.recorder.playback.take() {
+            for ((device, input), value) in playback.played {
+                if let Some(value) = value.rest() {
+                    self.inject(device, &input, value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.recorder.playback.is_some()
+    }
+
+    //Every input from the host passes through here before it is applied
+    pub(super) fn live(
+        &mut self,
+        device: Option<DeviceId>,
+        input: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        if let Some((start, recording)) = &mut self.recorder.recording {
+            recording.inputs.push(RecordedInput {
+                tick: self.recorder.tick - *start,
+                device,
+                input: input.clone(),
+                value,
+            });
+        }
+
+        if value.is_down()
+            && self
+                .recorder
+                .playback
+                .as_ref()
+                .is_some_and(|p| p.interruptible)
+        {
+            self.stop_playback()?;
+        }
+
+        Ok(())
+    }
+
+    //Plays every input due by this tick, then moves on to the next one
+    pub(super) fn advance_recorder(&mut self) {
+        if let Some(mut playback) = self.recorder.playback.take() {
+            let elapsed = self.recorder.tick - playback.start;
+
+            while let Some(recorded) = playback.recording.inputs.get(playback.next) {
+                if recorded.tick > elapsed {
+                    break;
+                }
+
+                if let Err(e) = self.inject(recorded.device, &recorded.input, recorded.value) {
+                    warn!("Failed to play back input: {:?}", e);
+                }
+
+                playback
+                    .played
+                    .insert((recorded.device, recorded.input.clone()), recorded.value);
+                playback.next += 1;
+            }
+
+            let finished = playback.next >= playback.recording.inputs.len();
+            self.recorder.playback = Some(playback);
+
+            if finished {
+                if let Err(e) = self.stop_playback() {
+                    warn!("Failed to stop playback: {:?}", e);
+                }
+            }
+        }
+
+        self.recorder.tick += 1;
+    }
+
+    fn inject(
+        &mut self,
+        device: Option<DeviceId>,
+        input: &C,
+        value: InputValue,
+    ) -> Result<(), InputError> {
+        match device {
+            Some(device) => self.route_device(device, input, value),
+            None => self.apply_input(None, input, value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    Parse(usize, String),
+}
@@ -0,0 +1,156 @@
+use super::InputValue;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Curve {
+    Linear,
+    Quadratic,
+    Cubic,
+    //Gets and returns a value in 0..=1
+    Custom(fn(f32) -> f32),
+}
+
+impl Curve {
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            Curve::Linear => value,
+            Curve::Quadratic => value * value,
+            Curve::Cubic => value * value * value,
+            Curve::Custom(f) => f(value).clamp(0.0, 1.0),
+        }
+    }
+}
+
+//Axial deadzones cut each axis on its own, radial ones cut the stick's length
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Deadzone {
+    None,
+    Axial(f32),
+    Radial(f32),
+}
+
+//Shapes raw Axis and Analog values before they reach an action
+#[derive(Clone, Copy, Debug)]
+pub struct AxisResponse {
+    pub deadzone: Deadzone,
+    //Raw values at or past this count as fully pushed
+    pub saturation: f32,
+    pub sensitivity: f32,
+    pub invert_x: bool,
+    pub invert_y: bool,
+    pub curve: Curve,
+}
+
+impl AxisResponse {
+    pub fn new() -> Self {
+        AxisResponse {
+            deadzone: Deadzone::None,
+            saturation: 1.0,
+            sensitivity: 1.0,
+            invert_x: false,
+            invert_y: false,
+            curve: Curve::Linear,
+        }
+    }
+
+    pub fn with_deadzone(mut self, deadzone: Deadzone) -> Self {
+        self.deadzone = deadzone;
+        self
+    }
+
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    pub fn with_inversion(mut self, invert_x: bool, invert_y: bool) -> Self {
+        self.invert_x = invert_x;
+        self.invert_y = invert_y;
+        self
+    }
+
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    pub fn apply(&self, value: InputValue) -> InputValue {
+        match value {
+            InputValue::Axis(v) => InputValue::Axis(self.axis(v)),
+            InputValue::Analog { x, y } => {
+                let (x, y) = self.analog(x, y);
+
+                InputValue::Analog { x, y }
+            }
+            _ => value,
+        }
+    }
+
+    pub fn axis(&self, value: f32) -> f32 {
+        let deadzone = match self.deadzone {
+            Deadzone::None => 0.0,
+            Deadzone::Axial(d) | Deadzone::Radial(d) => d,
+        };
+
+        let shaped = self.shape(value.abs(), deadzone) * value.signum();
+
+        match self.invert_x {
+            true => -shaped,
+            false => shaped,
+        }
+    }
+
+    pub fn analog(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = match self.deadzone {
+            Deadzone::Radial(deadzone) => {
+                let length = (x * x + y * y).sqrt();
+
+                match length > 0.0 {
+                    true => {
+                        let scale = self.shape(length, deadzone) / length;
+                        (x * scale, y * scale)
+                    }
+                    false => (0.0, 0.0),
+                }
+            }
+            Deadzone::Axial(deadzone) => (
+                self.shape(x.abs(), deadzone) * x.signum(),
+                self.shape(y.abs(), deadzone) * y.signum(),
+            ),
+            Deadzone::None => (
+                self.shape(x.abs(), 0.0) * x.signum(),
+                self.shape(y.abs(), 0.0) * y.signum(),
+            ),
+        };
+
+        (
+            if self.invert_x { -x } else { x },
+            if self.invert_y { -y } else { y },
+        )
+    }
+
+    //Remaps a magnitude from deadzone..saturation onto 0..1, then curves and scales it
+    fn shape(&self, magnitude: f32, deadzone: f32) -> f32 {
+        if magnitude <= deadzone {
+            return 0.0;
+        }
+
+        let range = self.saturation - deadzone;
+        let normalized = match range > 0.0 {
+            true => ((magnitude - deadzone) / range).min(1.0),
+            false => 1.0,
+        };
+
+        self.curve.apply(normalized) * self.sensitivity
+    }
+}
+
+impl Default for AxisResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
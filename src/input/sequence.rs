@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use super::ActionHandle;
+
+//Each step lists the actions that are held together, and nothing else the sequence watches,
+//so down, down+forward, forward, forward+punch reads like a fighting game motion.
+//Once one of its actions is removed, the sequence never matches again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sequence {
+    pub steps: Vec<Vec<ActionHandle>>,
+    //Most ticks from the first step to the last
+    pub window: u64,
+    //Most ticks between two steps
+    pub leniency: u64,
+}
+
+impl Sequence {
+    pub fn new(steps: Vec<Vec<ActionHandle>>, window: u64, leniency: u64) -> Self {
+        Sequence {
+            steps,
+            window,
+            leniency,
+        }
+    }
+
+    fn watches(&self, action: usize) -> bool {
+        self.steps.iter().flatten().any(|h| h.index() == action)
+    }
+
+    //The held actions this sequence cares about match the step exactly
+    fn step_matches(&self, step: &[ActionHandle], held: &[usize]) -> bool {
+        let watched: Vec<&usize> = held.iter().filter(|a| self.watches(**a)).collect();
+
+        watched.len() == step.len() && step.iter().all(|h| watched.contains(&&h.index()))
+    }
+}
+
+//An action going up or down, with every digital action held right after it
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequenceEdge {
+    pub tick: u64,
+    pub action: usize,
+    pub pressed: bool,
+    pub held: Vec<usize>,
+}
+
+pub(super) struct SequenceBuffer {
+    sequences: Vec<Sequence>,
+    //By sequence, the tick of its last match. Edges up to it were used up by that match.
+    consumed: Vec<u64>,
+    edges: VecDeque<SequenceEdge>,
+    matched: Vec<usize>,
+}
+
+impl SequenceBuffer {
+    pub fn new() -> Self {
+        SequenceBuffer {
+            sequences: Vec::new(),
+            consumed: Vec::new(),
+            edges: VecDeque::new(),
+            matched: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, sequence: Sequence) -> usize {
+        self.sequences.push(sequence);
+        self.consumed.push(0);
+
+        self.sequences.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    //Sequences using the action are emptied, and its edges are forgotten
+    //so the next action in its slot can't complete anything with them
+    pub fn remove_action(&mut self, action: usize) {
+        for sequence in self.sequences.iter_mut() {
+            if sequence.watches(action) {
+                sequence.steps.clear();
+            }
+        }
+
+        self.edges.retain(|e| e.action != action);
+        for edge in self.edges.iter_mut() {
+            edge.held.retain(|a| *a != action);
+        }
+    }
+
+    pub fn edges(&self) -> &VecDeque<SequenceEdge> {
+        &self.edges
+    }
+
+    pub fn matched(&self) -> &[usize] {
+        &self.matched
+    }
+
+    //Takes the edges of a single tick, then checks which sequences they complete
+    pub fn record(&mut self, tick: u64, edges: Vec<SequenceEdge>) {
+        self.matched.clear();
+
+        if edges.is_empty() {
+            return;
+        }
+
+        self.edges.extend(edges);
+
+        let oldest = self.sequences.iter().map(|s| s.window).max().unwrap_or(0);
+        while self.edges.front().is_some_and(|e| e.tick + oldest < tick) {
+            self.edges.pop_front();
+        }
+
+        for (i, sequence) in self.sequences.iter().enumerate() {
+            if Self::completes(sequence, &self.edges, tick, self.consumed[i]) {
+                self.consumed[i] = tick;
+                self.matched.push(i);
+            }
+        }
+    }
+
+    //Walks back from the newest edge of the actions the sequence watches,
+    //matching the steps from last to first
+    fn completes(
+        sequence: &Sequence,
+        edges: &VecDeque<SequenceEdge>,
+        tick: u64,
+        consumed: u64,
+    ) -> bool {
+        let mut steps = sequence.steps.iter().rev();
+        let mut edges = edges
+            .iter()
+            .rev()
+            .take_while(|e| e.tick + sequence.window >= tick && e.tick > consumed)
+            .filter(|e| sequence.watches(e.action));
+
+        let last = match (steps.next(), edges.next()) {
+            (Some(step), Some(edge))
+                if edge.tick == tick && sequence.step_matches(step, &edge.held) =>
+            {
+                edge.tick
+            }
+            _ => return false,
+        };
+
+        let mut previous = last;
+        for step in steps {
+            let found = edges
+                .by_ref()
+                .take_while(|e| e.tick + sequence.leniency >= previous)
+                .find(|e| sequence.step_matches(step, &e.held));
+
+            match found {
+                Some(edge) => previous = edge.tick,
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
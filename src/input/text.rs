@@ -0,0 +1,278 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EditKey {
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Home,
+    End,
+    Enter,
+}
+
+//What the host sends while text input is enabled
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextEvent {
+    Commit(String),
+    //Text the IME is still composing, with the cursor as a char index into it.
+    //Empty text ends the composition.
+    Compose { text: String, cursor: usize },
+    //Select extends the selection, like holding Shift
+    Edit { key: EditKey, select: bool },
+}
+
+//Like actions, events only show up after the next advance and last for a single update.
+//Pushing a context without pass-through keeps typing away from gameplay actions.
+pub struct TextInput {
+    enabled: bool,
+    pending: Vec<TextEvent>,
+    events: Vec<TextEvent>,
+    composition: Option<(String, usize)>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        TextInput {
+            enabled: false,
+            pending: Vec::new(),
+            events: Vec::new(),
+            composition: None,
+        }
+    }
+
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    //Drops anything not read yet, along with the composition
+    pub fn disable(&mut self) {
+        self.enabled = false;
+        self.pending.clear();
+        self.events.clear();
+        self.composition = None;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    //Ignored while disabled
+    pub fn push(&mut self, event: TextEvent) {
+        if self.enabled {
+            self.pending.push(event);
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.events = std::mem::take(&mut self.pending);
+
+        for event in self.events.iter() {
+            match event {
+                TextEvent::Commit(_) => self.composition = None,
+                TextEvent::Compose { text, .. } if text.is_empty() => self.composition = None,
+                TextEvent::Compose { text, cursor } => {
+                    self.composition = Some((text.clone(), *cursor))
+                }
+                TextEvent::Edit { .. } => {}
+            }
+        }
+    }
+
+    //Commits and edits of this update in the order they came in
+    pub fn events(&self) -> &[TextEvent] {
+        &self.events
+    }
+
+    pub fn committed(&self) -> String {
+        self.events
+            .iter()
+            .filter_map(|e| match e {
+                TextEvent::Commit(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn edits(&self) -> impl Iterator<Item = (EditKey, bool)> + '_ {
+        self.events.iter().filter_map(|e| match e {
+            TextEvent::Edit { key, select } => Some((*key, *select)),
+            _ => None,
+        })
+    }
+
+    pub fn composition(&self) -> Option<(&str, usize)> {
+        self.composition
+            .as_ref()
+            .map(|(text, cursor)| (text.as_str(), *cursor))
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//A single line of editable text. The cursor and selection are byte offsets
+//that always sit between chars.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineBuffer {
+    text: String,
+    cursor: usize,
+    //The end of the selection that stays put while the cursor moves
+    anchor: Option<usize>,
+    //In chars
+    max_len: Option<usize>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_string();
+        self.cursor = self.text.len();
+        self.anchor = None;
+        self
+    }
+
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor != self.cursor => {
+                Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some((start, end)) => &self.text[start..end],
+            None => "",
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.anchor = None;
+    }
+
+    //Replaces the selection, cutting the text short at the max length
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+
+        let room = match self.max_len {
+            Some(max_len) => max_len.saturating_sub(self.text.chars().count()),
+            None => usize::MAX,
+        };
+        let end = text.char_indices().nth(room).map_or(text.len(), |(i, _)| i);
+
+        self.text.insert_str(self.cursor, &text[..end]);
+        self.cursor += end;
+    }
+
+    //Returns true for Enter
+    pub fn edit(&mut self, key: EditKey, select: bool) -> bool {
+        match key {
+            EditKey::Backspace => {
+                if !self.delete_selection() {
+                    let start = self.previous(self.cursor);
+                    self.text.replace_range(start..self.cursor, "");
+                    self.cursor = start;
+                }
+            }
+            EditKey::Delete => {
+                if !self.delete_selection() {
+                    let end = self.next(self.cursor);
+                    self.text.replace_range(self.cursor..end, "");
+                }
+            }
+            EditKey::Left => match (self.selection(), select) {
+                (Some((start, _)), false) => self.move_to(start, false),
+                _ => self.move_to(self.previous(self.cursor), select),
+            },
+            EditKey::Right => match (self.selection(), select) {
+                (Some((_, end)), false) => self.move_to(end, false),
+                _ => self.move_to(self.next(self.cursor), select),
+            },
+            EditKey::Home => self.move_to(0, select),
+            EditKey::End => self.move_to(self.text.len(), select),
+            EditKey::Enter => return true,
+        }
+
+        false
+    }
+
+    //Applies the update's commits and edits, returning true if Enter was pressed
+    pub fn apply(&mut self, input: &TextInput) -> bool {
+        let mut entered = false;
+
+        for event in input.events() {
+            match event {
+                TextEvent::Commit(text) => self.insert(text),
+                TextEvent::Edit { key, select } => entered |= self.edit(*key, *select),
+                TextEvent::Compose { .. } => {}
+            }
+        }
+
+        entered
+    }
+
+    fn move_to(&mut self, position: usize, select: bool) {
+        match select {
+            true => {
+                self.anchor.get_or_insert(self.cursor);
+            }
+            false => self.anchor = None,
+        }
+
+        self.cursor = position;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+
+        match selection {
+            Some((start, end)) => {
+                self.text.replace_range(start..end, "");
+                self.cursor = start;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn previous(&self, position: usize) -> usize {
+        self.text[..position]
+            .chars()
+            .next_back()
+            .map_or(0, |c| position - c.len_utf8())
+    }
+
+    fn next(&self, position: usize) -> usize {
+        self.text[position..]
+            .chars()
+            .next()
+            .map_or(position, |c| position + c.len_utf8())
+    }
+}
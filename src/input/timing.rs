@@ -0,0 +1,98 @@
+//All times are in seconds, like the delta given to updates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureConfig {
+    pub double_tap_window: f64,
+    pub long_press: f64,
+    pub repeat_delay: f64,
+    //Repeats per second once the delay has passed
+    pub repeat_rate: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            double_tap_window: 0.3,
+            long_press: 0.5,
+            repeat_delay: 0.4,
+            repeat_rate: 10.0,
+        }
+    }
+}
+
+//What a digital action did over time, worked out once per update
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Timing {
+    pub config: GestureConfig,
+
+    pub held: f64,
+    pub double_tapped: bool,
+    pub long_pressed: bool,
+    pub repeated: bool,
+
+    was_down: bool,
+    since_press: f64,
+    long_press_fired: bool,
+    next_repeat: f64,
+}
+
+impl Timing {
+    pub fn new(config: GestureConfig) -> Self {
+        Timing {
+            config,
+
+            held: 0.0,
+            double_tapped: false,
+            long_pressed: false,
+            repeated: false,
+
+            was_down: false,
+            since_press: f64::INFINITY,
+            long_press_fired: false,
+            next_repeat: 0.0,
+        }
+    }
+
+    pub fn was_down(&self) -> bool {
+        self.was_down
+    }
+
+    pub fn advance(&mut self, down: bool, delta: f64) {
+        self.double_tapped = false;
+        self.long_pressed = false;
+        self.repeated = false;
+
+        self.since_press += delta;
+
+        match (self.was_down, down) {
+            (false, true) => {
+                self.held = 0.0;
+                self.double_tapped = self.since_press <= self.config.double_tap_window;
+                //A third quick press starts a new double tap instead of finishing another
+                self.since_press = match self.double_tapped {
+                    true => f64::INFINITY,
+                    false => 0.0,
+                };
+
+                self.long_press_fired = false;
+                self.repeated = true;
+                self.next_repeat = self.config.repeat_delay;
+            }
+            (true, true) => {
+                self.held += delta;
+
+                if !self.long_press_fired && self.held >= self.config.long_press {
+                    self.long_press_fired = true;
+                    self.long_pressed = true;
+                }
+
+                if self.config.repeat_rate > 0.0 && self.held >= self.next_repeat {
+                    self.repeated = true;
+                    self.next_repeat += 1.0 / self.config.repeat_rate;
+                }
+            }
+            (_, false) => self.held = 0.0,
+        }
+
+        self.was_down = down;
+    }
+}
@@ -0,0 +1,383 @@
+use std::{collections::HashSet, hash::Hash};
+
+use super::{InputError, InputMap, InputValue};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TouchPhase {
+    Began,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+//What the host sends for every change of a contact, in window pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchEvent {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub x: f32,
+    pub y: f32,
+}
+
+//A contact as of the last advance. Ended and cancelled contacts are kept for one update.
+//Contacts that are down but didn't just land are Moved, even when they stay still.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Touch {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub x: f32,
+    pub y: f32,
+    pub dx: f32,
+    pub dy: f32,
+    pub start: (f32, f32),
+    //Seconds since it began
+    pub duration: f64,
+
+    //Once part of a pinch or pan, it can't be a tap or swipe anymore
+    multi: bool,
+}
+
+impl Touch {
+    fn distance(&self) -> f32 {
+        let (x, y) = (self.x - self.start.0, self.y - self.start.1);
+
+        (x * x + y * y).sqrt()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    Tap { x: f32, y: f32 },
+    //From where it started, by how far it went
+    Swipe { x: f32, y: f32, dx: f32, dy: f32 },
+    //How much the distance between two fingers changed this update, around their middle
+    Pinch { x: f32, y: f32, scale: f32 },
+    Pan { dx: f32, dy: f32 },
+}
+
+//Times in seconds, distances in window pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchConfig {
+    pub tap_time: f64,
+    pub tap_distance: f32,
+    pub swipe_time: f64,
+    pub swipe_distance: f32,
+}
+
+impl Default for TouchConfig {
+    fn default() -> Self {
+        TouchConfig {
+            tap_time: 0.25,
+            tap_distance: 10.0,
+            swipe_time: 0.5,
+            swipe_distance: 50.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Region {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x && y >= self.y && x <= self.x + self.width && y <= self.y + self.height
+    }
+}
+
+//Virtual controls drive their input like any other device, so actions bind to them as usual.
+//A control belongs to the touch that began on it until that touch lifts.
+enum VirtualControl<C> {
+    //Down while its touch is inside the region
+    Button {
+        region: Region,
+        input: C,
+    },
+    //Analog, with full length at the radius
+    Stick {
+        center: (f32, f32),
+        radius: f32,
+        input: C,
+    },
+}
+
+impl<C> VirtualControl<C> {
+    fn input(&self) -> &C {
+        match self {
+            VirtualControl::Button { input, .. } | VirtualControl::Stick { input, .. } => input,
+        }
+    }
+
+    fn hit(&self, x: f32, y: f32) -> bool {
+        match self {
+            VirtualControl::Button { region, .. } => region.contains(x, y),
+            VirtualControl::Stick { center, radius, .. } => {
+                let (x, y) = (x - center.0, y - center.1);
+
+                x * x + y * y <= radius * radius
+            }
+        }
+    }
+
+    fn value(&self, x: f32, y: f32) -> InputValue {
+        match self {
+            VirtualControl::Button { region, .. } => InputValue::Digital(region.contains(x, y)),
+            VirtualControl::Stick { center, radius, .. } => {
+                let (x, y) = ((x - center.0) / radius, (y - center.1) / radius);
+                let length = (x * x + y * y).sqrt();
+
+                match length > 1.0 {
+                    true => InputValue::Analog {
+                        x: x / length,
+                        y: y / length,
+                    },
+                    false => InputValue::Analog { x, y },
+                }
+            }
+        }
+    }
+
+    fn released(&self) -> InputValue {
+        match self {
+            VirtualControl::Button { .. } => InputValue::Digital(false),
+            VirtualControl::Stick { .. } => InputValue::Analog { x: 0.0, y: 0.0 },
+        }
+    }
+}
+
+pub(super) struct Touches<C> {
+    config: TouchConfig,
+    pending: Vec<TouchEvent>,
+    touches: Vec<Touch>,
+    gestures: Vec<Gesture>,
+
+    controls: Vec<(VirtualControl<C>, Option<u64>)>,
+    //Touches that landed on a control, which never make gestures
+    claimed: HashSet<u64>,
+}
+
+impl<C> Touches<C> {
+    pub fn new() -> Self {
+        Touches {
+            config: TouchConfig::default(),
+            pending: Vec::new(),
+            touches: Vec::new(),
+            gestures: Vec::new(),
+
+            controls: Vec::new(),
+            claimed: HashSet::new(),
+        }
+    }
+
+    pub fn advance(&mut self, delta: f64) {
+        let claimed = &mut self.claimed;
+        self.touches.retain(|t| {
+            let alive = matches!(t.phase, TouchPhase::Began | TouchPhase::Moved);
+            if !alive {
+                claimed.remove(&t.id);
+            }
+
+            alive
+        });
+
+        //Began only lasts for the update the contact landed in
+        for touch in self.touches.iter_mut() {
+            if touch.phase == TouchPhase::Began {
+                touch.phase = TouchPhase::Moved;
+            }
+            touch.dx = 0.0;
+            touch.dy = 0.0;
+            touch.duration += delta;
+        }
+
+        for event in std::mem::take(&mut self.pending) {
+            self.apply(event);
+        }
+
+        self.recognize();
+    }
+
+    fn apply(&mut self, event: TouchEvent) {
+        match self.touches.iter_mut().find(|t| t.id == event.id) {
+            Some(touch) => {
+                touch.dx += event.x - touch.x;
+                touch.dy += event.y - touch.y;
+                touch.x = event.x;
+                touch.y = event.y;
+
+                touch.phase = match (touch.phase, event.phase) {
+                    (TouchPhase::Began, TouchPhase::Moved) => TouchPhase::Began,
+                    (_, phase) => phase,
+                };
+            }
+            None if event.phase == TouchPhase::Began => self.touches.push(Touch {
+                id: event.id,
+                phase: TouchPhase::Began,
+                x: event.x,
+                y: event.y,
+                dx: 0.0,
+                dy: 0.0,
+                start: (event.x, event.y),
+                duration: 0.0,
+
+                multi: false,
+            }),
+            //Whatever began before we were listening is ignored
+            None => {}
+        }
+    }
+
+    fn recognize(&mut self) {
+        self.gestures.clear();
+
+        let free: Vec<usize> = (0..self.touches.len())
+            .filter(|i| {
+                !self.claimed.contains(&self.touches[*i].id)
+                    && self.touches[*i].phase != TouchPhase::Cancelled
+            })
+            .collect();
+
+        if let [a, b] = free[..] {
+            self.two_fingers(a, b);
+        }
+
+        for i in free {
+            let touch = &self.touches[i];
+            if touch.phase != TouchPhase::Ended || touch.multi {
+                continue;
+            }
+
+            let distance = touch.distance();
+
+            if touch.duration <= self.config.tap_time && distance <= self.config.tap_distance {
+                self.gestures.push(Gesture::Tap {
+                    x: touch.x,
+                    y: touch.y,
+                });
+            } else if touch.duration <= self.config.swipe_time
+                && distance >= self.config.swipe_distance
+            {
+                self.gestures.push(Gesture::Swipe {
+                    x: touch.start.0,
+                    y: touch.start.1,
+                    dx: touch.x - touch.start.0,
+                    dy: touch.y - touch.start.1,
+                });
+            }
+        }
+    }
+
+    fn two_fingers(&mut self, a: usize, b: usize) {
+        let (first, second) = (self.touches[a], self.touches[b]);
+
+        self.touches[a].multi = true;
+        self.touches[b].multi = true;
+
+        let span =
+            |ax: f32, ay: f32, bx: f32, by: f32| ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt();
+        let before = span(
+            first.x - first.dx,
+            first.y - first.dy,
+            second.x - second.dx,
+            second.y - second.dy,
+        );
+        let after = span(first.x, first.y, second.x, second.y);
+
+        if before > 0.0 && after != before {
+            self.gestures.push(Gesture::Pinch {
+                x: (first.x + second.x) * 0.5,
+                y: (first.y + second.y) * 0.5,
+                scale: after / before,
+            });
+        }
+
+        let (dx, dy) = ((first.dx + second.dx) * 0.5, (first.dy + second.dy) * 0.5);
+        if dx != 0.0 || dy != 0.0 {
+            self.gestures.push(Gesture::Pan { dx, dy });
+        }
+    }
+}
+
+impl<C, A> InputMap<C, A>
+where
+    C: Hash + Eq + Clone,
+{
+    //Virtual controls see the event right away, touches and gestures after the next advance
+    pub fn process_touch(&mut self, event: TouchEvent) -> Result<(), InputError> {
+        self.touch.pending.push(event);
+
+        let mut inputs = Vec::new();
+        for (control, owner) in self.touch.controls.iter_mut() {
+            match (event.phase, *owner) {
+                (TouchPhase::Began, None) if control.hit(event.x, event.y) => {
+                    *owner = Some(event.id);
+                    self.touch.claimed.insert(event.id);
+                    inputs.push((control.input().clone(), control.value(event.x, event.y)));
+                }
+                (TouchPhase::Moved, Some(id)) if id == event.id => {
+                    inputs.push((control.input().clone(), control.value(event.x, event.y)));
+                }
+                (TouchPhase::Ended | TouchPhase::Cancelled, Some(id)) if id == event.id => {
+                    *owner = None;
+                    inputs.push((control.input().clone(), control.released()));
+                }
+                _ => {}
+            }
+        }
+
+        for (input, value) in inputs {
+            self.process(&input, value)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn touches(&self) -> &[Touch] {
+        &self.touch.touches
+    }
+
+    //Recognized during the last advance
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.touch.gestures
+    }
+
+    pub fn set_touch_config(&mut self, config: TouchConfig) {
+        self.touch.config = config;
+    }
+
+    //Sends Digital values to the input
+    pub fn add_virtual_button(&mut self, region: Region, input: C) -> usize {
+        self.touch
+            .controls
+            .push((VirtualControl::Button { region, input }, None));
+
+        self.touch.controls.len() - 1
+    }
+
+    //Sends Analog values to the input
+    pub fn add_virtual_stick(&mut self, center: (f32, f32), radius: f32, input: C) -> usize {
+        self.touch.controls.push((
+            VirtualControl::Stick {
+                center,
+                radius,
+                input,
+            },
+            None,
+        ));
+
+        self.touch.controls.len() - 1
+    }
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, hash::Hash};
 
 pub mod app;
+pub mod clock;
 pub mod scene;
 
 #[cfg(feature = "utility")]
@@ -18,12 +19,14 @@ pub mod input;
 #[cfg(feature = "loading")]
 pub mod loading;
 
+use app::Lifecycle;
 use scene::Scene;
 
 pub struct Stage<'a, Key, Initialize, Update, Message, Instruction, Draw, DrawBatch>
 where
     Key: Hash + Eq + ToString,
 {
+    #[allow(clippy::type_complexity)]
     scenes: HashMap<
         Key,
         Box<
@@ -83,8 +86,26 @@ where
         self.scenes.insert(key, scene);
     }
 
+    //From the bottom of the stage to the top
+    pub fn active(&self) -> &[Key] {
+        &self.active
+    }
+
+    pub fn initialize(&mut self, init: &mut Initialize) {
+        for scene in self.scenes.values_mut() {
+            scene.initialize(init);
+        }
+    }
+
+    //Every scene hears about it, active or not
+    pub fn lifecycle(&mut self, event: Lifecycle) {
+        for scene in self.scenes.values_mut() {
+            scene.lifecycle(event);
+        }
+    }
+
     pub fn update(&mut self, update: &Update, delta: f64) -> Result<Vec<Instruction>, StageError> {
-        if self.active.len() > 0 {
+        if !self.active.is_empty() {
             let mut instructions = Vec::new();
 
             let mut start = self.active.len() - 1;
@@ -137,7 +158,7 @@ where
     }
 
     pub fn draw(&self, draw: &Draw, interp: f64) -> Result<Vec<DrawBatch>, StageError> {
-        if self.active.len() > 0 {
+        if !self.active.is_empty() {
             let mut batches: Vec<DrawBatch> = Vec::new();
 
             let mut start = self.active.len() - 1;
@@ -156,6 +177,16 @@ where
     }
 }
 
+impl<'a, Key, Initialize, Update, Message, Instruction, Draw, DrawBatch> Default
+    for Stage<'a, Key, Initialize, Update, Message, Instruction, Draw, DrawBatch>
+where
+    Key: Clone + Hash + Eq + ToString,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug)]
 pub enum StageError {
     NoScenesToUpdateError,
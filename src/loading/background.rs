@@ -0,0 +1,227 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use super::resources::ResourceLoadError;
+
+//Loads on worker threads, so it owns its arguments instead of borrowing them.
+//Resources that have to be made on the owning thread, like textures, are made in finish.
+pub trait AsyncResourceLoader<R>: Send + Sync + 'static {
+    type Arguments: Send + 'static;
+    type Data: Send + 'static;
+
+    fn load(&self, args: &Self::Arguments) -> Result<Self::Data, ResourceLoadError>;
+
+    fn finish(&self, data: Self::Data) -> Result<R, ResourceLoadError>;
+
+    //Counted towards the progress once the data is loaded
+    fn bytes(&self, _data: &Self::Data) -> u64 {
+        0
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct WorkerPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.max(1))
+            .map(|_| {
+                let receiver = receiver.clone();
+
+                thread::spawn(move || loop {
+                    let job = match receiver.lock() {
+                        Ok(receiver) => receiver.recv(),
+                        Err(_) => return,
+                    };
+
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                })
+            })
+            .collect();
+
+        WorkerPool {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    fn execute(&self, job: Job) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(job);
+        }
+    }
+}
+
+//Lets the workers finish what they were given, then joins them
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.sender.take();
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+//A loader shared with a pool of worker threads, which any number of storages can load through
+pub struct BackgroundLoader<L> {
+    loader: Arc<L>,
+    pool: WorkerPool,
+}
+
+impl<L> BackgroundLoader<L> {
+    pub fn new(loader: L, threads: usize) -> Self {
+        BackgroundLoader {
+            loader: Arc::new(loader),
+            pool: WorkerPool::new(threads),
+        }
+    }
+
+    pub fn loader(&self) -> &L {
+        &self.loader
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LoadProgress {
+    //Inserted into the storage, failed, or cancelled
+    pub done: usize,
+    pub failed: usize,
+    pub total: usize,
+    pub bytes: u64,
+}
+
+impl LoadProgress {
+    pub fn is_complete(&self) -> bool {
+        self.done == self.total
+    }
+
+    //In 0..=1, complete when nothing was requested
+    pub fn fraction(&self) -> f32 {
+        match self.total {
+            0 => 1.0,
+            total => self.done as f32 / total as f32,
+        }
+    }
+}
+
+type Finish<R> = Box<dyn FnOnce() -> Result<R, ResourceLoadError> + Send>;
+
+pub(super) struct Loaded<K, R> {
+    id: u64,
+    pub key: K,
+    pub bytes: u64,
+    pub result: Result<Finish<R>, ResourceLoadError>,
+}
+
+//The storage's end of the background loads
+pub(super) struct Background<K, R> {
+    sender: Sender<Loaded<K, R>>,
+    receiver: Receiver<Loaded<K, R>>,
+    pub progress: LoadProgress,
+    //Requests still out, by id and key. Cancelled ones are dropped from here
+    //and ignored once they finish.
+    pending: Vec<(u64, String)>,
+    next: u64,
+}
+
+impl<K, R> Background<K, R> {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Background {
+            sender,
+            receiver,
+            progress: LoadProgress::default(),
+            pending: Vec::new(),
+            next: 0,
+        }
+    }
+
+    pub fn is_pending(&self, key: &str) -> bool {
+        self.pending.iter().any(|(_, k)| k == key)
+    }
+
+    //Whatever the workers make of it is thrown away
+    pub fn cancel(&mut self, key: &str) -> bool {
+        match self.pending.iter().position(|(_, k)| k == key) {
+            Some(position) => {
+                self.pending.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<K, R> Background<K, R>
+where
+    K: ToString,
+{
+    pub fn request<L>(&mut self, background: &BackgroundLoader<L>, key: K, args: L::Arguments)
+    where
+        L: AsyncResourceLoader<R>,
+        K: Send + 'static,
+        R: 'static,
+    {
+        let id = self.next;
+        self.next += 1;
+        self.pending.push((id, key.to_string()));
+        //A fresh batch starts once the last one is complete
+        if self.progress.is_complete() {
+            self.progress = LoadProgress::default();
+        }
+        self.progress.total += 1;
+
+        let loader = background.loader.clone();
+        let sender = self.sender.clone();
+
+        background.pool.execute(Box::new(move || {
+            let mut bytes = 0;
+            let result = loader.load(&args).map(|data| {
+                bytes = loader.bytes(&data);
+
+                Box::new(move || loader.finish(data)) as Finish<R>
+            });
+
+            let _ = sender.send(Loaded {
+                id,
+                key,
+                bytes,
+                result,
+            });
+        }));
+    }
+
+    //Everything the workers finished since the last call, leaving out cancelled loads
+    pub fn finished(&mut self) -> Vec<Loaded<K, R>> {
+        let mut finished = Vec::new();
+
+        for loaded in self.receiver.try_iter() {
+            self.progress.done += 1;
+            self.progress.bytes += loaded.bytes;
+
+            if let Some(position) = self.pending.iter().position(|(id, _)| *id == loaded.id) {
+                self.pending.remove(position);
+                finished.push(loaded);
+            }
+        }
+
+        finished
+    }
+}
@@ -1,14 +1,65 @@
 use log::error;
-use std::{borrow::Borrow, hash::Hash, string::ToString};
+use std::{borrow::Borrow, hash::Hash, marker::PhantomData, rc::Rc, string::ToString};
 use uuid::Uuid;
 
+pub mod background;
+pub mod registry;
 pub mod resources;
 
+//Tickets carry the generation of the slot they were taken for and go stale
+//once its resource is replaced or unloaded, no matter what happens to the rest.
+//Typed by the resource, so they only fit storages of that resource.
+//Every clone keeps the resource from being auto unloaded until it is dropped.
+pub struct Ticket<R: ?Sized> {
+    ticket: AnyTicket,
+    holder: Rc<()>,
+    resource: PhantomData<fn() -> R>,
+}
+
+impl<R: ?Sized> Clone for Ticket<R> {
+    fn clone(&self) -> Self {
+        Ticket {
+            ticket: self.ticket,
+            holder: self.holder.clone(),
+            resource: PhantomData,
+        }
+    }
+}
+
+impl<R: ?Sized> Ticket<R> {
+    //Unchecked, storages and the registry make sure the resource type fits first
+    pub(crate) fn new(ticket: AnyTicket, holder: Rc<()>) -> Self {
+        Ticket {
+            ticket,
+            holder,
+            resource: PhantomData,
+        }
+    }
+
+    //The erased ticket does not keep the resource loaded
+    pub fn erase(&self) -> AnyTicket {
+        self.ticket
+    }
+
+    //Unlike erase, still keeps the resource loaded
+    pub(crate) fn cast<T: ?Sized>(self) -> Ticket<T> {
+        Ticket::new(self.ticket, self.holder)
+    }
+}
+
+//A ticket of any resource, for lists that mix them like draw instructions.
+//The storage is still checked when it is used.
 #[derive(Clone, Copy)]
-pub struct Ticket {
+pub struct AnyTicket {
     index: usize,
     storage_uuid: Uuid,
-    storage_lock: u32,
+    generation: u32,
+}
+
+impl<R: ?Sized> From<Ticket<R>> for AnyTicket {
+    fn from(ticket: Ticket<R>) -> Self {
+        ticket.erase()
+    }
 }
 
 pub trait TicketManager<SK, SKB, K, KB>
@@ -17,17 +68,20 @@ where
     KB: Hash + Eq + ToString + ?Sized,
     K: Hash + Eq + ToString + Borrow<KB>,
 {
-    fn get_ticket_with_key(
+    //Fails with WrongStorage when the storage holds some other resource type
+    fn get_ticket_with_key<R: 'static>(
         &self,
         storage_key: &SKB,
         resource_key: &KB,
-    ) -> Result<Ticket, ResourceError>;
+    ) -> Result<Ticket<R>, ResourceError>;
 }
 
 #[derive(Debug)]
 pub enum ResourceError {
     NotStored(String),
-    StorageUnlocked,
+    StorageBorrowed(String),
+    StorageExists(String),
+    StorageLocked,
     TicketOutdated,
     UnknownStorage(String),
     WrongStorage,
@@ -39,7 +93,9 @@ impl ResourceError {
         {
 
             ResourceError::NotStored(err) => error!("Missing resource with key: {}", err),
-            ResourceError::StorageUnlocked => error!("Attempting to access unlocked storage."),
+            ResourceError::StorageBorrowed(err) => error!("Attempting to access storage already borrowed with key: {}", err),
+            ResourceError::StorageExists(err) => error!("Attempting to add storage with taken key: {}", err),
+            ResourceError::StorageLocked => error!("Attempting to change locked storage."),
             ResourceError::TicketOutdated => error!("Attempting to access storage with outdated ticket."),
             ResourceError::UnknownStorage(err) => error!("Attempting to access storage with unknown key: {}", err),
             ResourceError::WrongStorage => error!("Attempting to access storage with ticket corresponding to the wrong resource type.")
@@ -0,0 +1,170 @@
+use std::{
+    any::{Any, TypeId},
+    borrow::Borrow,
+    cell::RefCell,
+    collections::HashMap,
+    hash::Hash,
+    rc::Rc,
+};
+
+use super::{
+    resources::{ResourceLoader, ResourceStorage},
+    ResourceError, Ticket, TicketManager,
+};
+
+//What the registry needs from a storage without knowing its resource type
+pub trait AnyStorage<KB: ?Sized> {
+    fn resource_type(&self) -> TypeId;
+
+    fn take_any_ticket(&self, key: &KB) -> Result<Ticket<dyn Any>, ResourceError>;
+
+    fn lock(&mut self);
+
+    fn unlock(&mut self);
+}
+
+impl<'a, K, R, L, KB> AnyStorage<KB> for ResourceStorage<'a, K, R, L>
+where
+    K: Hash + Eq + ToString + Borrow<KB>,
+    R: 'static,
+    L: ResourceLoader<'a, R>,
+    KB: Hash + Eq + ToString + ?Sized,
+{
+    fn resource_type(&self) -> TypeId {
+        TypeId::of::<R>()
+    }
+
+    fn take_any_ticket(&self, key: &KB) -> Result<Ticket<dyn Any>, ResourceError> {
+        self.take_ticket(key).map(|t| t.cast())
+    }
+
+    fn lock(&mut self) {
+        ResourceStorage::lock(self);
+    }
+
+    fn unlock(&mut self) {
+        ResourceStorage::unlock(self);
+    }
+}
+
+type SharedStorage<'a, KB> = Rc<RefCell<dyn AnyStorage<KB> + 'a>>;
+
+//Storages of any resource type, looked up by a storage key like StorageType.
+//The registry shares each storage with whoever added it, who keeps the typed handle
+//to load into it and to get resources by ticket.
+pub struct ResourceRegistry<'a, SK, KB: ?Sized> {
+    storages: HashMap<SK, SharedStorage<'a, KB>>,
+    locked: bool,
+}
+
+impl<'a, SK, KB> ResourceRegistry<'a, SK, KB>
+where
+    SK: Hash + Eq + ToString,
+    KB: ?Sized,
+{
+    pub fn new() -> Self {
+        ResourceRegistry {
+            storages: HashMap::new(),
+            locked: false,
+        }
+    }
+
+    //Storages added while the registry is locked are locked as well
+    pub fn add_storage<S>(
+        &mut self,
+        storage_key: SK,
+        storage: S,
+    ) -> Result<Rc<RefCell<S>>, ResourceError>
+    where
+        S: AnyStorage<KB> + 'a,
+    {
+        if self.storages.contains_key(&storage_key) {
+            return Err(ResourceError::StorageExists(storage_key.to_string()));
+        }
+
+        let storage = Rc::new(RefCell::new(storage));
+        if self.locked {
+            storage.borrow_mut().lock();
+        }
+
+        self.storages.insert(storage_key, storage.clone());
+
+        Ok(storage)
+    }
+
+    pub fn remove_storage<SKB>(&mut self, storage_key: &SKB) -> Result<(), ResourceError>
+    where
+        SK: Borrow<SKB>,
+        SKB: Hash + Eq + ToString + ?Sized,
+    {
+        match self.storages.remove(storage_key) {
+            Some(_) => Ok(()),
+            None => Err(ResourceError::UnknownStorage(storage_key.to_string())),
+        }
+    }
+
+    pub fn contains_storage<SKB>(&self, storage_key: &SKB) -> bool
+    where
+        SK: Borrow<SKB>,
+        SKB: Hash + Eq + ?Sized,
+    {
+        self.storages.contains_key(storage_key)
+    }
+
+    pub fn lock(&mut self) {
+        self.locked = true;
+
+        for storage in self.storages.values() {
+            storage.borrow_mut().lock();
+        }
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+
+        for storage in self.storages.values() {
+            storage.borrow_mut().unlock();
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl<'a, SK, KB> Default for ResourceRegistry<'a, SK, KB>
+where
+    SK: Hash + Eq + ToString,
+    KB: ?Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, SK, SKB, K, KB> TicketManager<SK, SKB, K, KB> for ResourceRegistry<'a, SK, KB>
+where
+    SK: Hash + Eq + ToString + Borrow<SKB>,
+    SKB: Hash + Eq + ToString + ?Sized,
+    K: Hash + Eq + ToString + Borrow<KB>,
+    KB: Hash + Eq + ToString + ?Sized,
+{
+    fn get_ticket_with_key<R: 'static>(
+        &self,
+        storage_key: &SKB,
+        resource_key: &KB,
+    ) -> Result<Ticket<R>, ResourceError> {
+        //Whoever added the storage may still be loading into it
+        let storage = match self.storages.get(storage_key).map(|s| s.try_borrow()) {
+            Some(Ok(storage)) => storage,
+            Some(Err(_)) => return Err(ResourceError::StorageBorrowed(storage_key.to_string())),
+            None => return Err(ResourceError::UnknownStorage(storage_key.to_string())),
+        };
+
+        if storage.resource_type() != TypeId::of::<R>() {
+            return Err(ResourceError::WrongStorage);
+        }
+
+        storage.take_any_ticket(resource_key).map(|t| t.cast())
+    }
+}
@@ -1,9 +1,17 @@
 use std::{
-    borrow::Borrow, cell::RefCell, collections::HashMap, hash::Hash, rc::Rc, string::ToString,
+    borrow::Borrow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    hash::Hash,
+    rc::Rc,
+    string::ToString,
 };
 use uuid::Uuid;
 
-use super::{ResourceError, Ticket};
+use super::{
+    background::{AsyncResourceLoader, Background, BackgroundLoader, LoadProgress},
+    AnyTicket, ResourceError, Ticket,
+};
 
 pub trait ResourceLoader<'a, R> {
     type Arguments: ?Sized;
@@ -11,18 +19,32 @@ pub trait ResourceLoader<'a, R> {
     fn load(&'a self, args: &Self::Arguments) -> Result<R, ResourceLoadError>;
 }
 
+//Unloaded slots are reused by later loads, with a new generation so older tickets go stale
+struct Slot<R> {
+    resource: Option<Rc<RefCell<R>>>,
+    generation: u32,
+    //Shared by every ticket of the current generation, so its count is the tickets still held
+    holders: Rc<()>,
+    //Set once a ticket or handle was given out, so auto unload doesn't get resources nobody
+    //asked for yet
+    used: Cell<bool>,
+}
+
 pub struct ResourceStorage<'a, K, R, L>
 where
     K: Hash + Eq + ToString,
     L: 'a + ResourceLoader<'a, R>,
 {
     loader: &'a L,
-    store: Vec<Rc<RefCell<R>>>,
+    store: Vec<Slot<R>>,
     map: HashMap<K, usize>,
+    free: Vec<usize>,
 
-    num_locks: u32,
     locked: bool,
+    auto_unload: bool,
     uuid: Uuid,
+
+    background: Background<K, R>,
 }
 
 impl<'a, K, R, L> ResourceStorage<'a, K, R, L>
@@ -34,20 +56,32 @@ where
         ResourceStorage {
             loader,
             locked: false,
-            num_locks: 0,
+            auto_unload: false,
             map: HashMap::new(),
             store: Vec::new(),
+            free: Vec::new(),
             uuid: uuid::Uuid::new_v4(),
+            background: Background::new(),
         }
     }
 
+    //Locking is optional. A locked storage refuses to load or unload and holds on to
+    //background loads until it is unlocked, so nothing changes while tickets are handed out.
     pub fn lock(&mut self) {
         self.locked = true;
     }
 
     pub fn unlock(&mut self) {
         self.locked = false;
-        self.num_locks += 1;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    //Makes every poll unload the resources nobody holds a ticket or handle to anymore
+    pub fn set_auto_unload(&mut self, auto_unload: bool) {
+        self.auto_unload = auto_unload;
     }
 
     pub fn load<A>(&mut self, key: K, args: &A) -> Result<(), ResourceLoadError>
@@ -55,61 +89,300 @@ where
         A: ?Sized,
         L: ResourceLoader<'a, R, Arguments = A>,
     {
-        if self.map.contains_key(&key) {
-            return Err(ResourceLoadError::AlreadyExists(key.to_string()));
+        self.check_new(&key)?;
+
+        let resource = self.loader.load(args)?;
+        self.insert(key, resource);
+
+        Ok(())
+    }
+
+    //Loads the key whether it exists or not, cancelling a background load of it.
+    //Tickets to the old resource go stale, while handles keep it alive until they are dropped.
+    pub fn replace<A>(&mut self, key: K, args: &A) -> Result<(), ResourceLoadError>
+    where
+        A: ?Sized,
+        L: ResourceLoader<'a, R, Arguments = A>,
+    {
+        if self.locked {
+            return Err(ResourceLoadError::StorageLocked);
+        }
+
+        let resource = self.loader.load(args)?;
+        self.background.cancel(&key.to_string());
+
+        match self.map.get(&key) {
+            Some(index) => {
+                let slot = &mut self.store[*index];
+                slot.resource = Some(Rc::new(RefCell::new(resource)));
+                slot.generation += 1;
+                slot.holders = Rc::new(());
+                slot.used.set(false);
+            }
+            None => self.insert(key, resource),
+        }
+
+        Ok(())
+    }
+
+    //Loads the resource again in place, so tickets and handles see the new one
+    pub fn reload<A>(&mut self, key: &K, args: &A) -> Result<(), ResourceLoadError>
+    where
+        A: ?Sized,
+        L: ResourceLoader<'a, R, Arguments = A>,
+    {
+        if self.locked {
+            return Err(ResourceLoadError::StorageLocked);
+        }
+
+        let index = match self.map.get(key) {
+            Some(index) => *index,
+            None => return Err(ResourceLoadError::NotStored(key.to_string())),
+        };
+
+        let resource = self.loader.load(args)?;
+        if let Some(stored) = &self.store[index].resource {
+            *stored.borrow_mut() = resource;
+        }
+
+        Ok(())
+    }
+
+    //Tickets to the resource go stale right away, handles keep it alive until they are dropped.
+    //A background load of the key is cancelled.
+    pub fn unload<KB>(&mut self, key: &KB) -> Result<(), ResourceError>
+    where
+        KB: Hash + Eq + ToString + ?Sized,
+        K: Borrow<KB>,
+    {
+        if self.locked {
+            return Err(ResourceError::StorageLocked);
+        }
+
+        let cancelled = self.background.cancel(&key.to_string());
+
+        match self.map.remove(key) {
+            Some(index) => {
+                self.free_slot(index);
+                Ok(())
+            }
+            None if cancelled => Ok(()),
+            None => Err(ResourceError::NotStored(key.to_string())),
+        }
+    }
+
+    //Unloads every resource that was used and has neither tickets nor handles left,
+    //returning how many went
+    pub fn unload_unused(&mut self) -> usize {
+        if self.locked {
+            return 0;
+        }
+
+        let store = &self.store;
+        let mut unused = Vec::new();
+        self.map.retain(|_, index| {
+            let slot = &store[*index];
+            let keep = !slot.used.get()
+                || Rc::strong_count(&slot.holders) > 1
+                || slot
+                    .resource
+                    .as_ref()
+                    .is_some_and(|r| Rc::strong_count(r) > 1);
+            if !keep {
+                unused.push(*index);
+            }
+
+            keep
+        });
+
+        for index in unused.iter() {
+            self.free_slot(*index);
+        }
+
+        unused.len()
+    }
+
+    //The resource shows up in the storage once a poll after the worker is done with it
+    pub fn load_async<AL>(
+        &mut self,
+        loader: &BackgroundLoader<AL>,
+        key: K,
+        args: AL::Arguments,
+    ) -> Result<(), ResourceLoadError>
+    where
+        AL: AsyncResourceLoader<R>,
+        K: Send + 'static,
+        R: 'static,
+    {
+        self.check_new(&key)?;
+        self.background.request(loader, key, args);
+
+        Ok(())
+    }
+
+    //Has to be called on the thread that owns the storage, inserting everything the workers
+    //finished. Returns the loads that failed.
+    pub fn poll(&mut self) -> Vec<ResourceLoadError> {
+        let mut failures = Vec::new();
+        if self.locked {
+            return failures;
+        }
+
+        for loaded in self.background.finished() {
+            match loaded.result.and_then(|finish| finish()) {
+                Ok(resource) => self.insert(loaded.key, resource),
+                Err(e) => failures.push(e),
+            }
+        }
+
+        self.background.progress.failed += failures.len();
+
+        if self.auto_unload {
+            self.unload_unused();
         }
 
-        self.store
-            .push(Rc::new(RefCell::new(self.loader.load(args)?)));
-        self.map.insert(key, self.store.len() - 1);
+        failures
+    }
+
+    pub fn progress(&self) -> LoadProgress {
+        self.background.progress
+    }
+
+    //True once every background load was polled
+    pub fn is_loading_complete(&self) -> bool {
+        self.background.progress.is_complete()
+    }
+
+    fn check_new(&self, key: &K) -> Result<(), ResourceLoadError> {
+        if self.locked {
+            return Err(ResourceLoadError::StorageLocked);
+        }
+
+        let name = key.to_string();
+
+        if self.map.contains_key(key) || self.background.is_pending(&name) {
+            return Err(ResourceLoadError::AlreadyExists(name));
+        }
 
         Ok(())
     }
 
+    fn insert(&mut self, key: K, resource: R) {
+        let resource = Some(Rc::new(RefCell::new(resource)));
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.store[index].resource = resource;
+                index
+            }
+            None => {
+                self.store.push(Slot {
+                    resource,
+                    generation: 0,
+                    holders: Rc::new(()),
+                    used: Cell::new(false),
+                });
+                self.store.len() - 1
+            }
+        };
+
+        self.map.insert(key, index);
+    }
+
+    fn free_slot(&mut self, index: usize) {
+        let slot = &mut self.store[index];
+        slot.resource = None;
+        slot.generation += 1;
+        slot.holders = Rc::new(());
+        slot.used.set(false);
+
+        self.free.push(index);
+    }
+
+    pub fn contains_key<KB>(&self, key: &KB) -> bool
+    where
+        KB: Hash + Eq + ?Sized,
+        K: Borrow<KB>,
+    {
+        self.map.contains_key(key)
+    }
+
     pub fn get_by_key<KB>(&self, key: &KB) -> Result<Rc<RefCell<R>>, ResourceError>
     where
         KB: Hash + Eq + ToString + ?Sized,
         K: Borrow<KB>,
     {
         match self.map.get(key) {
-            Some(r) => Ok(self.store[*r].clone()),
+            Some(r) => {
+                let slot = &self.store[*r];
+                slot.used.set(true);
+
+                slot.resource
+                    .clone()
+                    .ok_or_else(|| ResourceError::NotStored(key.to_string()))
+            }
             None => Err(ResourceError::NotStored(key.to_string())),
         }
     }
 
-    pub fn take_ticket<KB>(&self, key: &KB) -> Result<Ticket, ResourceError>
+    //Keeps the resource from being auto unloaded until the ticket and its clones are dropped
+    pub fn take_ticket<KB>(&self, key: &KB) -> Result<Ticket<R>, ResourceError>
     where
         KB: Hash + Eq + ToString + ?Sized,
         K: Borrow<KB>,
     {
         match self.map.get(key) {
-            Some(r) => Ok(Ticket {
+            Some(r) => Ok(self.ticket(AnyTicket {
                 index: *r,
-                storage_lock: self.num_locks,
                 storage_uuid: self.uuid,
-            }),
+                generation: self.store[*r].generation,
+            })),
             None => Err(ResourceError::NotStored(key.to_string())),
         }
     }
 
-    pub fn get_by_ticket(&self, ticket: Ticket) -> Result<Rc<RefCell<R>>, ResourceError> {
-        if !self.locked {
-            return Err(ResourceError::StorageUnlocked);
-        }
+    //Types a ticket taken from a list that mixes resources, like draw instructions
+    pub fn check_any_ticket(&self, ticket: AnyTicket) -> Result<Ticket<R>, ResourceError> {
+        self.check_ticket(ticket)?;
 
+        Ok(self.ticket(ticket))
+    }
+
+    pub fn get_by_ticket(&self, ticket: &Ticket<R>) -> Result<Rc<RefCell<R>>, ResourceError> {
+        let ticket = ticket.erase();
+        self.check_ticket(ticket)?;
+
+        self.store[ticket.index]
+            .resource
+            .clone()
+            .ok_or(ResourceError::TicketOutdated)
+    }
+
+    //Panics if the resource was unloaded
+    pub fn get_by_ticket_unchecked(&self, ticket: &Ticket<R>) -> Rc<RefCell<R>> {
+        self.store[ticket.erase().index]
+            .resource
+            .clone()
+            .expect("Resource was unloaded")
+    }
+
+    fn ticket<T: ?Sized>(&self, ticket: AnyTicket) -> Ticket<T> {
+        let slot = &self.store[ticket.index];
+        slot.used.set(true);
+
+        Ticket::new(ticket, slot.holders.clone())
+    }
+
+    fn check_ticket(&self, ticket: AnyTicket) -> Result<(), ResourceError> {
         if self.uuid != ticket.storage_uuid {
             return Err(ResourceError::WrongStorage);
         }
 
-        if self.num_locks != ticket.storage_lock {
+        if self.store[ticket.index].generation != ticket.generation {
             return Err(ResourceError::TicketOutdated);
         }
 
-        Ok(self.store[ticket.index].clone())
-    }
-
-    pub fn get_by_ticket_unchecked(&self, ticket: Ticket) -> Rc<RefCell<R>> {
-        self.store[ticket.index].clone()
+        Ok(())
     }
 }
 
@@ -117,4 +390,6 @@ where
 pub enum ResourceLoadError {
     AlreadyExists(String),
     LoadFailure(String),
+    NotStored(String),
+    StorageLocked,
 }
@@ -1,4 +1,4 @@
-use crate::Response;
+use crate::{app::Lifecycle, Response};
 
 pub trait Scene {
     type Key;
@@ -24,4 +24,7 @@ pub trait Scene {
     fn covering(&self) -> bool;
 
     fn blocking(&self) -> bool;
+
+    //Called as soon as it happens, no update runs while the app is suspended
+    fn lifecycle(&mut self, _event: Lifecycle) {}
 }
@@ -1,7 +1,12 @@
 use core::fmt;
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{input::InputMap, loading::Ticket};
+use crate::{
+    input::{InputMap, UserHandle},
+    loading::AnyTicket,
+};
+
+pub mod stage_app;
 
 #[derive(Clone, Copy, Eq, Hash, PartialEq)]
 pub enum StorageType {
@@ -28,15 +33,24 @@ impl fmt::Display for StorageType {
     }
 }
 
-pub struct Initialize<I, S, C> {
-    pub input: Rc<RefCell<InputMap<I>>>,
+//The resource type a backend keeps under each StorageType, so scenes can hold typed tickets
+pub trait StorageResources {
+    type Data: 'static;
+    type Font: 'static;
+    type Music: 'static;
+    type Sound: 'static;
+    type Texture: 'static;
+}
+
+pub struct Initialize<I, S, C, A = String> {
+    pub input: Rc<RefCell<InputMap<I, A>>>,
     pub storage: Rc<RefCell<S>>,
     pub content: Rc<RefCell<C>>,
 }
 
-impl<I, S, C> Initialize<I, S, C> {
+impl<I, S, C, A> Initialize<I, S, C, A> {
     pub fn new(
-        input: Rc<RefCell<InputMap<I>>>,
+        input: Rc<RefCell<InputMap<I, A>>>,
         storage: Rc<RefCell<S>>,
         content: Rc<RefCell<C>>,
     ) -> Self {
@@ -48,15 +62,15 @@ impl<I, S, C> Initialize<I, S, C> {
     }
 }
 
-pub struct Update<I, C> {
-    pub input: Rc<RefCell<InputMap<I>>>,
+pub struct Update<I, C, A = String> {
+    pub input: Rc<RefCell<InputMap<I, A>>>,
     pub info: Rc<RefCell<Vec<UpdateInfo>>>,
     pub content: Rc<RefCell<C>>,
 }
 
-impl<I, C> Update<I, C> {
+impl<I, C, A> Update<I, C, A> {
     pub fn new(
-        input: Rc<RefCell<InputMap<I>>>,
+        input: Rc<RefCell<InputMap<I, A>>>,
         info: Rc<RefCell<Vec<UpdateInfo>>>,
         content: Rc<RefCell<C>>,
     ) -> Self {
@@ -68,11 +82,17 @@ impl<I, C> Update<I, C> {
     }
 }
 
+//Lifecycle events go to Scene::lifecycle instead, they would only reach
+//scenes here once the app was already resumed
 pub enum UpdateInfo {
     MusicStopped,
+    //So scenes can pause while a player has no controller
+    UserJoined(UserHandle),
+    UserDisconnected(UserHandle),
+    UserReconnected(UserHandle),
 }
 
 pub enum UpdateInstruction {
-    PlayMusic(Ticket, i32, f32),
-    PlaySound(Ticket, f32),
+    PlayMusic(AnyTicket, i32, f32),
+    PlaySound(AnyTicket, f32),
 }
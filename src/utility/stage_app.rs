@@ -0,0 +1,423 @@
+use log::error;
+use std::{cell::RefCell, convert::Infallible, hash::Hash, rc::Rc};
+
+use crate::{
+    app::{App, Lifecycle},
+    clock::{Clock, InstantClock},
+    input::{device::DeviceEvent, InputMap},
+    Stage,
+};
+
+use super::{Initialize, Update, UpdateInfo};
+
+pub trait EventBackend<I, A = String> {
+    type Error;
+
+    //Returns false when the app should quit
+    fn poll(&mut self, input: &mut InputMap<I, A>) -> Result<bool, Self::Error>;
+
+    fn lifecycle(&mut self) -> Vec<Lifecycle> {
+        Vec::new()
+    }
+
+    //Called after every update to set the controllers' motors from InputMap::motors
+    fn haptics(&mut self, _input: &InputMap<I, A>) {}
+}
+
+pub trait InstructionBackend<Instruction, S> {
+    fn handle(&mut self, instructions: Vec<Instruction>, storage: &S, info: &mut Vec<UpdateInfo>);
+
+    //For pausing audio and the like when the app loses focus or is suspended
+    fn lifecycle(&mut self, _event: Lifecycle, _storage: &S) {}
+
+    fn shutdown(&mut self, _storage: &S) {}
+}
+
+pub trait RenderBackend<DrawBatch, S> {
+    fn render(&mut self, batches: Vec<DrawBatch>, storage: &S, interp: f64);
+}
+
+//Never produces events, optionally quitting after a number of polls
+pub struct NullEvents {
+    remaining: Option<u64>,
+}
+
+impl NullEvents {
+    pub fn new() -> Self {
+        NullEvents { remaining: None }
+    }
+
+    pub fn limited(polls: u64) -> Self {
+        NullEvents {
+            remaining: Some(polls),
+        }
+    }
+}
+
+impl Default for NullEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, A> EventBackend<I, A> for NullEvents {
+    type Error = Infallible;
+
+    fn poll(&mut self, _input: &mut InputMap<I, A>) -> Result<bool, Self::Error> {
+        match &mut self.remaining {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct NullInstructions;
+
+impl<Instruction, S> InstructionBackend<Instruction, S> for NullInstructions {
+    fn handle(
+        &mut self,
+        _instructions: Vec<Instruction>,
+        _storage: &S,
+        _info: &mut Vec<UpdateInfo>,
+    ) {
+    }
+}
+
+#[derive(Default)]
+pub struct NullRenderer;
+
+impl<DrawBatch, S> RenderBackend<DrawBatch, S> for NullRenderer {
+    fn render(&mut self, _batches: Vec<DrawBatch>, _storage: &S, _interp: f64) {}
+}
+
+pub type AppStage<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, A = String> =
+    Stage<'a, Key, Initialize<I, S, C, A>, Update<I, C, A>, Message, Instruction, Draw, DrawBatch>;
+
+pub struct StageApp<
+    'a,
+    Key,
+    I,
+    S,
+    C,
+    Message,
+    Instruction,
+    Draw,
+    DrawBatch,
+    T = InstantClock,
+    E = NullEvents,
+    H = NullInstructions,
+    R = NullRenderer,
+    A = String,
+> where
+    Key: Hash + Eq + ToString,
+{
+    pub stage: AppStage<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, A>,
+    pub draw: Draw,
+
+    pub input: Rc<RefCell<InputMap<I, A>>>,
+    pub storage: Rc<RefCell<S>>,
+    pub content: Rc<RefCell<C>>,
+    pub info: Rc<RefCell<Vec<UpdateInfo>>>,
+
+    //Input contexts pushed while their scene is active
+    contexts: Vec<(Key, String)>,
+
+    clock: T,
+    events: E,
+    instructions: H,
+    renderer: R,
+}
+
+impl<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, A>
+    StageApp<
+        'a,
+        Key,
+        I,
+        S,
+        C,
+        Message,
+        Instruction,
+        Draw,
+        DrawBatch,
+        InstantClock,
+        NullEvents,
+        NullInstructions,
+        NullRenderer,
+        A,
+    >
+where
+    Key: Clone + Hash + Eq + ToString,
+{
+    //Headless by default, swap in real backends with the with_* methods
+    pub fn new(
+        stage: AppStage<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, A>,
+        draw: Draw,
+        input: InputMap<I, A>,
+        storage: S,
+        content: C,
+    ) -> Self {
+        StageApp {
+            stage,
+            draw,
+
+            input: Rc::new(RefCell::new(input)),
+            storage: Rc::new(RefCell::new(storage)),
+            content: Rc::new(RefCell::new(content)),
+            info: Rc::new(RefCell::new(Vec::new())),
+
+            contexts: Vec::new(),
+
+            clock: InstantClock::new(),
+            events: NullEvents::new(),
+            instructions: NullInstructions,
+            renderer: NullRenderer,
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+impl<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H, R, A>
+    StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H, R, A>
+where
+    Key: Clone + Hash + Eq + ToString,
+{
+    pub fn with_clock<T2: Clock>(
+        self,
+        clock: T2,
+    ) -> StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T2, E, H, R, A> {
+        StageApp {
+            stage: self.stage,
+            draw: self.draw,
+            input: self.input,
+            storage: self.storage,
+            content: self.content,
+            info: self.info,
+            contexts: self.contexts,
+            clock,
+            events: self.events,
+            instructions: self.instructions,
+            renderer: self.renderer,
+        }
+    }
+
+    pub fn with_events<E2: EventBackend<I, A>>(
+        self,
+        events: E2,
+    ) -> StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E2, H, R, A> {
+        StageApp {
+            stage: self.stage,
+            draw: self.draw,
+            input: self.input,
+            storage: self.storage,
+            content: self.content,
+            info: self.info,
+            contexts: self.contexts,
+            clock: self.clock,
+            events,
+            instructions: self.instructions,
+            renderer: self.renderer,
+        }
+    }
+
+    pub fn with_instructions<H2: InstructionBackend<Instruction, S>>(
+        self,
+        instructions: H2,
+    ) -> StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H2, R, A> {
+        StageApp {
+            stage: self.stage,
+            draw: self.draw,
+            input: self.input,
+            storage: self.storage,
+            content: self.content,
+            info: self.info,
+            contexts: self.contexts,
+            clock: self.clock,
+            events: self.events,
+            instructions,
+            renderer: self.renderer,
+        }
+    }
+
+    pub fn with_renderer<R2: RenderBackend<DrawBatch, S>>(
+        self,
+        renderer: R2,
+    ) -> StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H, R2, A> {
+        StageApp {
+            stage: self.stage,
+            draw: self.draw,
+            input: self.input,
+            storage: self.storage,
+            content: self.content,
+            info: self.info,
+            contexts: self.contexts,
+            clock: self.clock,
+            events: self.events,
+            instructions: self.instructions,
+            renderer,
+        }
+    }
+
+    pub fn clock(&self) -> &T {
+        &self.clock
+    }
+
+    pub fn events(&mut self) -> &mut E {
+        &mut self.events
+    }
+
+    pub fn instructions(&mut self) -> &mut H {
+        &mut self.instructions
+    }
+
+    pub fn renderer(&mut self) -> &mut R {
+        &mut self.renderer
+    }
+
+    pub fn bind_context(&mut self, scene: Key, context: &str) {
+        self.contexts.push((scene, context.to_string()));
+    }
+
+    fn initialize(&mut self) {
+        let mut init = Initialize::new(
+            self.input.clone(),
+            self.storage.clone(),
+            self.content.clone(),
+        );
+
+        self.stage.initialize(&mut init);
+    }
+
+    fn update_handles(&self) -> Update<I, C, A> {
+        Update::new(self.input.clone(), self.info.clone(), self.content.clone())
+    }
+
+    //Contexts follow their scenes in the order the scenes were activated
+    fn sync_contexts(&mut self)
+    where
+        I: Hash + Eq + Clone,
+    {
+        let mut input = self.input.borrow_mut();
+
+        for (scene, context) in self.contexts.iter() {
+            if !self.stage.active().contains(scene) && input.is_context_active(context) {
+                if let Err(e) = input.remove_context(context) {
+                    error!("Failed to remove input context {}: {:?}", context, e);
+                }
+            }
+        }
+
+        for scene in self.stage.active() {
+            for (_, context) in self.contexts.iter().filter(|(s, _)| s == scene) {
+                if !input.is_context_active(context) {
+                    if let Err(e) = input.push_context(context) {
+                        error!("Failed to push input context {}: {:?}", context, e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn notify(&mut self, event: Lifecycle)
+    where
+        H: InstructionBackend<Instruction, S>,
+    {
+        self.stage.lifecycle(event);
+        self.instructions.lifecycle(event, &self.storage.borrow());
+    }
+}
+
+impl<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H, R, A> App
+    for StageApp<'a, Key, I, S, C, Message, Instruction, Draw, DrawBatch, T, E, H, R, A>
+where
+    Key: Clone + Hash + Eq + ToString,
+    I: Hash + Eq + Clone,
+    T: Clock,
+    E: EventBackend<I, A>,
+    H: InstructionBackend<Instruction, S>,
+    R: RenderBackend<DrawBatch, S>,
+{
+    type EventError = E::Error;
+
+    fn ticks(&self) -> u64 {
+        self.clock.ticks()
+    }
+
+    fn processed_events(&mut self) -> Result<bool, Self::EventError> {
+        let mut input = self.input.borrow_mut();
+        let running = self.events.poll(&mut input);
+
+        let mut info = self.info.borrow_mut();
+        for event in input.take_device_events() {
+            info.push(match event {
+                DeviceEvent::Joined { user, .. } => UpdateInfo::UserJoined(user),
+                DeviceEvent::Disconnected { user, .. } => UpdateInfo::UserDisconnected(user),
+                DeviceEvent::Reconnected { user, .. } => UpdateInfo::UserReconnected(user),
+            });
+        }
+
+        running
+    }
+
+    fn update(&mut self, delta: f64) {
+        let update = self.update_handles();
+        self.input.borrow_mut().advance(delta);
+
+        match self.stage.update(&update, delta) {
+            Ok(instructions) => {
+                let mut info = self.info.borrow_mut();
+                info.clear();
+
+                self.instructions
+                    .handle(instructions, &self.storage.borrow(), &mut info);
+            }
+            Err(e) => error!("Stage failed to update: {:?}", e),
+        }
+
+        self.sync_contexts();
+        self.events.haptics(&self.input.borrow());
+    }
+
+    fn draw(&mut self, interp: f64, _total_time: u64) {
+        match self.stage.draw(&self.draw, interp) {
+            Ok(batches) => self
+                .renderer
+                .render(batches, &self.storage.borrow(), interp),
+            Err(e) => error!("Stage failed to draw: {:?}", e),
+        }
+    }
+
+    fn lifecycle_events(&mut self) -> Vec<Lifecycle> {
+        self.events.lifecycle()
+    }
+
+    //Scenes get their tickets here, so the storage must be filled before the loop starts
+    fn start(&mut self) {
+        self.initialize();
+        self.sync_contexts();
+    }
+
+    fn shutdown(&mut self) {
+        self.instructions.shutdown(&self.storage.borrow());
+    }
+
+    fn focus_lost(&mut self) {
+        self.notify(Lifecycle::FocusLost);
+    }
+
+    fn focus_gained(&mut self) {
+        self.notify(Lifecycle::FocusGained);
+    }
+
+    fn suspend(&mut self) {
+        self.notify(Lifecycle::Suspended);
+    }
+
+    fn resume(&mut self) {
+        self.notify(Lifecycle::Resumed);
+    }
+}
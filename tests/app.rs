@@ -0,0 +1,105 @@
+use std::rc::Rc;
+
+use stagehand::{
+    app::{gameloop, gameloop_with_clock, App, Lifecycle},
+    clock::ManualClock,
+};
+
+//Steps the clock from a script of tick values and quits when it runs out
+struct Scripted {
+    clock: Rc<ManualClock>,
+    script: Vec<u64>,
+    updates: u32,
+}
+
+impl App for Scripted {
+    type EventError = ();
+
+    fn ticks(&self) -> u64 {
+        0
+    }
+
+    fn processed_events(&mut self) -> Result<bool, ()> {
+        match self.script.pop() {
+            Some(ticks) => {
+                self.clock.set(ticks);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn update(&mut self, _delta: f64) {
+        self.updates += 1;
+    }
+
+    fn draw(&mut self, _interp: f64, _total_time: u64) {}
+}
+
+#[test]
+fn rewinding_the_clock_does_not_panic() {
+    let clock = Rc::new(ManualClock::new(1000));
+    let mut app = Scripted {
+        clock: clock.clone(),
+        //Popped from the back
+        script: vec![250, 250, 0, 1000],
+        updates: 0,
+    };
+
+    gameloop_with_clock(&mut app, clock.as_ref(), 10).unwrap();
+
+    //Only the 250 ms after the rewind count, two full steps
+    assert_eq!(app.updates, 2);
+}
+
+//Suspends on the first loop and quits after a few more
+struct Sleeper {
+    loops: u32,
+    waits: Vec<u64>,
+    updates: u32,
+}
+
+impl App for Sleeper {
+    type EventError = ();
+
+    fn ticks(&self) -> u64 {
+        self.loops as u64 * 100
+    }
+
+    fn processed_events(&mut self) -> Result<bool, ()> {
+        self.loops += 1;
+
+        Ok(self.loops <= 4)
+    }
+
+    fn update(&mut self, _delta: f64) {
+        self.updates += 1;
+    }
+
+    fn draw(&mut self, _interp: f64, _total_time: u64) {}
+
+    fn lifecycle_events(&mut self) -> Vec<Lifecycle> {
+        match self.loops {
+            1 => vec![Lifecycle::Suspended],
+            _ => Vec::new(),
+        }
+    }
+
+    fn wait_while_suspended(&mut self, step: u64) {
+        self.waits.push(step);
+    }
+}
+
+#[test]
+fn suspended_loops_wait_instead_of_updating() {
+    let mut app = Sleeper {
+        loops: 0,
+        waits: Vec::new(),
+        updates: 0,
+    };
+
+    gameloop(&mut app, 10).unwrap();
+
+    assert_eq!(app.waits, vec![100; 4]);
+    assert_eq!(app.updates, 0);
+}
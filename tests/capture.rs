@@ -0,0 +1,37 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{is_down, map, set, state};
+use stagehand::input::{binding::Binding, ActionState};
+
+#[test]
+fn actions_held_when_capture_starts_are_released() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    set(&mut map, "Space", true);
+    map.advance(0.0);
+    assert!(is_down(&map, jump));
+
+    map.capture_next();
+    set(&mut map, "Space", false);
+    map.advance(0.0);
+    map.advance(0.0);
+
+    assert!(state(&map, jump) == ActionState::Up);
+    assert!(map.is_capturing());
+}
+
+#[test]
+fn captured_presses_do_not_reach_actions() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    map.capture_next();
+    set(&mut map, "Space", true);
+    map.advance(0.0);
+
+    assert!(!is_down(&map, jump));
+    assert!(map.take_captured() == Some(Binding::Single("Space")));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{digital, is_down, set, Map};
+use stagehand::input::{binding::Chord, ActionHandle};
+
+//S, Ctrl+S and Ctrl+Shift+S on the same user
+fn map() -> (Map, [ActionHandle; 3]) {
+    let mut map = Map::new();
+    let user = map.add_user();
+
+    let s = map
+        .add_action(user, "S".to_string(), vec!["S"], digital())
+        .unwrap();
+    let save = map
+        .add_action(
+            user,
+            "Save".to_string(),
+            vec![Chord::new(vec!["Ctrl"], "S")],
+            digital(),
+        )
+        .unwrap();
+    let save_as = map
+        .add_action(
+            user,
+            "SaveAs".to_string(),
+            vec![Chord::new(vec!["Ctrl", "Shift"], "S")],
+            digital(),
+        )
+        .unwrap();
+
+    (map, [s, save, save_as])
+}
+
+fn down(map: &Map, actions: [ActionHandle; 3]) -> [bool; 3] {
+    actions.map(|a| is_down(map, a))
+}
+
+#[test]
+fn longer_chords_win_over_their_subsets() {
+    let (mut map, actions) = map();
+
+    set(&mut map, "Ctrl", true);
+    set(&mut map, "S", true);
+    map.advance(0.016);
+    assert_eq!(down(&map, actions), [false, true, false]);
+
+    set(&mut map, "Shift", true);
+    map.advance(0.016);
+    assert_eq!(down(&map, actions), [false, false, true]);
+}
+
+#[test]
+fn letting_go_of_a_modifier_falls_back_to_the_shorter_chord() {
+    let (mut map, actions) = map();
+
+    for input in ["Ctrl", "Shift", "S"] {
+        set(&mut map, input, true);
+    }
+    map.advance(0.016);
+    assert_eq!(down(&map, actions), [false, false, true]);
+
+    set(&mut map, "Shift", false);
+    map.advance(0.016);
+    assert_eq!(down(&map, actions), [false, true, false]);
+
+    set(&mut map, "Ctrl", false);
+    map.advance(0.016);
+    assert_eq!(down(&map, actions), [true, false, false]);
+}
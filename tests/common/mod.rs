@@ -0,0 +1,41 @@
+//Fixtures shared by the input tests, each test file only uses some of them
+#![allow(dead_code)]
+
+use stagehand::input::{ActionHandle, ActionState, ActionType, InputMap, InputValue, UserHandle};
+
+pub type Map = InputMap<&'static str>;
+
+pub fn digital() -> ActionType {
+    ActionType::Digital(ActionState::Up)
+}
+
+//One user with a digital action on each input, named after the input
+pub fn map(inputs: &[&'static str]) -> (Map, UserHandle, Vec<ActionHandle>) {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+
+    let actions = inputs
+        .iter()
+        .map(|input| {
+            map.add_action(user, input.to_string(), vec![*input], digital())
+                .unwrap()
+        })
+        .collect();
+
+    (map, user, actions)
+}
+
+pub fn set(map: &mut Map, input: &'static str, down: bool) {
+    map.process(&input, InputValue::Digital(down)).unwrap();
+}
+
+pub fn state(map: &Map, handle: ActionHandle) -> ActionState {
+    match map.get_action_by_handle(handle).unwrap() {
+        ActionType::Digital(state) => state,
+        _ => panic!("The action should stay digital"),
+    }
+}
+
+pub fn is_down(map: &Map, handle: ActionHandle) -> bool {
+    map.get_action_by_handle(handle).unwrap().is_down()
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{set, Map};
+use stagehand::input::{
+    binding::{Composite, Opposites},
+    ActionHandle, ActionType,
+};
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+fn axis_map(opposites: Opposites) -> (Map, ActionHandle) {
+    let mut map = Map::new();
+    let user = map.add_user();
+    let steer = map
+        .add_action(
+            user,
+            "Steer".to_string(),
+            vec![Composite::axis("Left", "Right").with_opposites(opposites)],
+            ActionType::Axis(0.0),
+        )
+        .unwrap();
+
+    (map, steer)
+}
+
+fn axis(map: &Map, handle: ActionHandle) -> f32 {
+    match map.get_action_by_handle(handle).unwrap() {
+        ActionType::Axis(value) => value,
+        _ => panic!("Steer should stay an axis"),
+    }
+}
+
+fn analog(map: &Map, handle: ActionHandle) -> (f32, f32) {
+    match map.get_action_by_handle(handle).unwrap() {
+        ActionType::Analog { x, y } => (x, y),
+        _ => panic!("Walk should stay analog"),
+    }
+}
+
+//Left goes down first, then Right joins it
+fn both_held(opposites: Opposites) -> f32 {
+    let (mut map, steer) = axis_map(opposites);
+
+    set(&mut map, "Left", true);
+    map.advance(0.016);
+    assert!(axis(&map, steer) == -1.0);
+
+    set(&mut map, "Right", true);
+    map.advance(0.016);
+
+    axis(&map, steer)
+}
+
+#[test]
+fn opposites_decide_what_both_keys_do() {
+    assert!(both_held(Opposites::LastWins) == 1.0);
+    assert!(both_held(Opposites::FirstWins) == -1.0);
+    assert!(both_held(Opposites::Cancel) == 0.0);
+}
+
+#[test]
+fn last_wins_goes_back_to_the_key_still_held() {
+    let (mut map, steer) = axis_map(Opposites::LastWins);
+
+    set(&mut map, "Right", true);
+    set(&mut map, "Left", true);
+    map.advance(0.016);
+    assert!(axis(&map, steer) == -1.0);
+
+    set(&mut map, "Left", false);
+    map.advance(0.016);
+    assert!(axis(&map, steer) == 1.0);
+}
+
+#[test]
+fn diagonals_are_normalized() {
+    let mut map = Map::new();
+    let user = map.add_user();
+    let walk = map
+        .add_action(
+            user,
+            "Walk".to_string(),
+            vec![Composite::directions("A", "D", "W", "S")],
+            ActionType::Analog { x: 0.0, y: 0.0 },
+        )
+        .unwrap();
+
+    set(&mut map, "D", true);
+    map.advance(0.016);
+    assert!(analog(&map, walk) == (1.0, 0.0));
+
+    set(&mut map, "W", true);
+    map.advance(0.016);
+
+    let (x, y) = analog(&map, walk);
+    let side = std::f32::consts::FRAC_1_SQRT_2;
+    assert!(close(x, side) && close(y, -side));
+    assert!(close(x * x + y * y, 1.0));
+}
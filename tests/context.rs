@@ -0,0 +1,153 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{digital, is_down};
+use stagehand::input::{
+    binding::Composite, context::DEFAULT_CONTEXT, ActionHandle, ActionType, InputMap, InputValue,
+};
+
+fn analog(map: &InputMap<&'static str>, handle: ActionHandle) -> (f32, f32) {
+    match map.get_action_by_handle(handle).unwrap() {
+        ActionType::Analog { x, y } => (x, y),
+        _ => panic!("Walk should stay analog"),
+    }
+}
+
+//Jump in gameplay and Confirm in the menu share Space, Pause is only bound in gameplay
+fn map(
+    pass_through: bool,
+) -> (
+    InputMap<&'static str>,
+    ActionHandle,
+    ActionHandle,
+    ActionHandle,
+) {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    map.add_context("menu", pass_through);
+
+    let jump = map
+        .add_action(user, "Jump".to_string(), vec!["Space"], digital())
+        .unwrap();
+    let pause = map
+        .add_action(user, "Pause".to_string(), vec!["Escape"], digital())
+        .unwrap();
+    let confirm = map
+        .add_action_in_context(
+            "menu",
+            user,
+            "Confirm".to_string(),
+            vec!["Space"],
+            digital(),
+        )
+        .unwrap();
+
+    (map, jump, pause, confirm)
+}
+
+#[test]
+fn the_top_context_takes_shared_inputs() {
+    let (mut map, jump, _, confirm) = map(false);
+
+    map.push_context("menu").unwrap();
+    map.process(&"Space", InputValue::Digital(true)).unwrap();
+    map.advance(0.0);
+
+    assert!(is_down(&map, confirm));
+    assert!(!is_down(&map, jump));
+}
+
+#[test]
+fn pass_through_lets_unbound_inputs_reach_lower_contexts() {
+    let (mut blocking, _, pause, _) = map(false);
+    blocking.push_context("menu").unwrap();
+    blocking
+        .process(&"Escape", InputValue::Digital(true))
+        .unwrap();
+    blocking.advance(0.0);
+    assert!(!is_down(&blocking, pause));
+
+    let (mut passing, jump, pause, _) = map(true);
+    passing.push_context("menu").unwrap();
+    passing
+        .process(&"Escape", InputValue::Digital(true))
+        .unwrap();
+    passing
+        .process(&"Space", InputValue::Digital(true))
+        .unwrap();
+    passing.advance(0.0);
+    assert!(is_down(&passing, pause));
+    assert!(!is_down(&passing, jump));
+}
+
+#[test]
+fn popping_hands_held_inputs_back() {
+    let (mut map, jump, _, confirm) = map(false);
+
+    map.push_context("menu").unwrap();
+    map.process(&"Space", InputValue::Digital(true)).unwrap();
+    map.advance(0.0);
+
+    assert_eq!(map.pop_context().unwrap().as_deref(), Some("menu"));
+    map.advance(0.0);
+    map.advance(0.0);
+
+    assert!(!is_down(&map, confirm));
+    assert!(is_down(&map, jump));
+}
+
+#[test]
+fn the_default_context_is_never_popped() {
+    let (mut map, _, _, _) = map(false);
+
+    map.push_context("menu").unwrap();
+    map.push_context(DEFAULT_CONTEXT).unwrap();
+    assert_eq!(
+        map.active_contexts().collect::<Vec<_>>(),
+        vec![DEFAULT_CONTEXT, "menu"]
+    );
+
+    assert_eq!(map.pop_context().unwrap().as_deref(), Some("menu"));
+    assert_eq!(map.pop_context().unwrap(), None);
+    assert_eq!(
+        map.active_contexts().collect::<Vec<_>>(),
+        vec![DEFAULT_CONTEXT]
+    );
+
+    map.remove_context(DEFAULT_CONTEXT).unwrap();
+    assert!(map.is_context_active(DEFAULT_CONTEXT));
+}
+
+#[test]
+fn overlays_only_shadow_the_directions_they_bind() {
+    let mut map: InputMap<&'static str> = InputMap::new();
+    let user = map.add_user();
+    map.add_context("overlay", true);
+
+    let walk = map
+        .add_action(
+            user,
+            "Walk".to_string(),
+            vec![Composite::directions("A", "D", "W", "S")],
+            ActionType::Analog { x: 0.0, y: 0.0 },
+        )
+        .unwrap();
+    let zoom = map
+        .add_action_in_context("overlay", user, "Zoom".to_string(), vec!["W"], digital())
+        .unwrap();
+
+    map.push_context("overlay").unwrap();
+    map.process(&"D", InputValue::Digital(true)).unwrap();
+    map.process(&"W", InputValue::Digital(true)).unwrap();
+    map.advance(0.0);
+
+    assert!(is_down(&map, zoom));
+    assert!(analog(&map, walk) == (1.0, 0.0));
+
+    map.pop_context().unwrap();
+    map.advance(0.0);
+
+    let (x, y) = analog(&map, walk);
+    assert!(x > 0.0 && y < 0.0);
+}
@@ -0,0 +1,165 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{digital, is_down};
+use stagehand::input::{
+    binding::Chord,
+    device::{DeviceEvent, DeviceId},
+    ActionHandle, InputMap, InputValue,
+};
+
+//Two users with Jump on South
+fn map() -> (InputMap<&'static str>, ActionHandle, ActionHandle) {
+    let mut map = InputMap::new();
+    let first = map.add_user();
+    let second = map.add_user();
+
+    let first = map
+        .add_action(first, "Jump".to_string(), vec!["South"], digital())
+        .unwrap();
+    let second = map
+        .add_action(second, "Jump".to_string(), vec!["South"], digital())
+        .unwrap();
+
+    (map, first, second)
+}
+
+#[test]
+fn claimed_devices_only_reach_their_user() {
+    let (mut map, first, second) = map();
+    map.claim_device(first.user(), DeviceId(1)).unwrap();
+    map.claim_device(second.user(), DeviceId(2)).unwrap();
+
+    assert_eq!(
+        map.claim_device(second.user(), DeviceId(1)),
+        Err(stagehand::input::InputError::DeviceAlreadyClaimed)
+    );
+
+    map.process_from(DeviceId(2), &"South", InputValue::Digital(true))
+        .unwrap();
+    map.advance(0.0);
+
+    assert!(!is_down(&map, first));
+    assert!(is_down(&map, second));
+}
+
+#[test]
+fn disconnecting_one_device_keeps_the_others_held() {
+    let (mut map, first, _) = map();
+    map.claim_device(first.user(), DeviceId(1)).unwrap();
+    map.claim_device(first.user(), DeviceId(2)).unwrap();
+
+    map.process_from(DeviceId(2), &"South", InputValue::Digital(true))
+        .unwrap();
+    map.advance(0.0);
+
+    map.disconnect_device(DeviceId(1)).unwrap();
+    map.advance(0.0);
+    assert!(is_down(&map, first));
+    assert!(!map.is_user_disconnected(first.user()).unwrap());
+
+    map.disconnect_device(DeviceId(2)).unwrap();
+    map.advance(0.0);
+    assert!(!is_down(&map, first));
+    assert!(map.is_user_disconnected(first.user()).unwrap());
+}
+
+#[test]
+fn inputs_held_on_two_devices_last_until_both_let_go() {
+    let (mut map, first, _) = map();
+    map.claim_device(first.user(), DeviceId(1)).unwrap();
+    map.claim_device(first.user(), DeviceId(2)).unwrap();
+
+    map.process_from(DeviceId(1), &"South", InputValue::Digital(true))
+        .unwrap();
+    map.process_from(DeviceId(2), &"South", InputValue::Digital(true))
+        .unwrap();
+    map.advance(0.0);
+
+    map.release_device(DeviceId(1)).unwrap();
+    map.advance(0.0);
+    assert!(is_down(&map, first));
+}
+
+#[test]
+fn join_disconnect_and_reconnect() {
+    let (mut map, first, second) = map();
+    map.set_join_input(Some("Start"));
+
+    //Only the join input assigns a device, and the press doesn't reach any action
+    map.process_from(DeviceId(7), &"South", InputValue::Digital(true))
+        .unwrap();
+    assert_eq!(map.device_owner(DeviceId(7)), None);
+
+    map.process_from(DeviceId(7), &"Start", InputValue::Digital(true))
+        .unwrap();
+    map.process_from(DeviceId(8), &"Start", InputValue::Digital(true))
+        .unwrap();
+    assert_eq!(map.device_owner(DeviceId(7)), Some(first.user()));
+    assert_eq!(map.device_owner(DeviceId(8)), Some(second.user()));
+
+    map.disconnect_device(DeviceId(7)).unwrap();
+    map.connect_device(DeviceId(7));
+
+    assert_eq!(
+        map.take_device_events(),
+        vec![
+            DeviceEvent::Joined {
+                user: first.user(),
+                device: DeviceId(7)
+            },
+            DeviceEvent::Joined {
+                user: second.user(),
+                device: DeviceId(8)
+            },
+            DeviceEvent::Disconnected {
+                user: first.user(),
+                device: DeviceId(7)
+            },
+            DeviceEvent::Reconnected {
+                user: first.user(),
+                device: DeviceId(7)
+            },
+        ]
+    );
+    assert_eq!(map.device_owner(DeviceId(7)), Some(first.user()));
+}
+
+#[test]
+fn disconnected_users_take_the_next_device_pressed() {
+    let (mut map, first, _) = map();
+    map.claim_device(first.user(), DeviceId(1)).unwrap();
+    map.disconnect_device(DeviceId(1)).unwrap();
+
+    map.process_from(DeviceId(3), &"South", InputValue::Digital(true))
+        .unwrap();
+    map.advance(0.0);
+
+    assert_eq!(map.device_owner(DeviceId(3)), Some(first.user()));
+    assert!(!map.is_user_disconnected(first.user()).unwrap());
+    assert!(!is_down(&map, first));
+}
+
+#[test]
+fn users_in_reused_slots_see_shared_inputs_already_held() {
+    let (mut map, first, _) = map();
+    map.process(&"Shift", InputValue::Digital(true)).unwrap();
+
+    map.remove_user(first.user()).unwrap();
+    let user = map.add_user();
+    assert_eq!(user.index(), first.user().index());
+
+    let run = map
+        .add_action(
+            user,
+            "Run".to_string(),
+            vec![Chord::new(vec!["Shift"], "Up")],
+            digital(),
+        )
+        .unwrap();
+    map.process(&"Up", InputValue::Digital(true)).unwrap();
+    map.advance(0.0);
+
+    assert!(is_down(&map, run));
+}
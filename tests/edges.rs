@@ -0,0 +1,80 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{map, set, state};
+use stagehand::input::ActionState;
+
+#[test]
+fn press_and_release_between_updates_are_both_seen() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    set(&mut map, "Space", true);
+    set(&mut map, "Space", false);
+
+    map.advance(0.016);
+    assert!(state(&map, jump) == ActionState::Pressed);
+    map.advance(0.016);
+    assert!(state(&map, jump) == ActionState::Released);
+    map.advance(0.016);
+    assert!(state(&map, jump) == ActionState::Up);
+}
+
+#[test]
+fn every_edge_gets_its_own_update() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    for down in [true, false, true, false] {
+        set(&mut map, "Space", down);
+    }
+
+    let mut seen = Vec::new();
+    for _ in 0..5 {
+        map.advance(0.016);
+        seen.push(state(&map, jump));
+    }
+
+    assert!(
+        seen == vec![
+            ActionState::Pressed,
+            ActionState::Released,
+            ActionState::Pressed,
+            ActionState::Released,
+            ActionState::Up,
+        ]
+    );
+}
+
+#[test]
+fn held_actions_stay_down_without_new_edges() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    set(&mut map, "Space", true);
+    map.advance(0.016);
+    assert!(state(&map, jump) == ActionState::Pressed);
+
+    //Repeats from the host while held are not edges
+    set(&mut map, "Space", true);
+    map.advance(0.016);
+    map.advance(0.016);
+    assert!(state(&map, jump) == ActionState::Down);
+}
+
+#[test]
+fn raw_state_runs_ahead_of_the_update() {
+    let (mut map, _, actions) = map(&["Space"]);
+    let jump = actions[0];
+
+    set(&mut map, "Space", true);
+
+    assert!(map
+        .user(jump.user())
+        .unwrap()
+        .get_raw_action_by_handle(jump)
+        .unwrap()
+        .is_down());
+    assert!(state(&map, jump) == ActionState::Up);
+}
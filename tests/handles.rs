@@ -0,0 +1,138 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::digital;
+use stagehand::input::{
+    binding::Binding, device::DeviceId, haptics::Rumble, response::AxisResponse,
+    sequence::Sequence, InputError, InputMap, InputValue,
+};
+
+#[test]
+fn stale_action_handles_are_rejected_after_reuse() {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    let old = map
+        .add_action(user, "Old".to_string(), vec!["A"], digital())
+        .unwrap();
+    map.remove_action(old).unwrap();
+
+    let new = map
+        .add_action(user, "New".to_string(), vec!["B"], digital())
+        .unwrap();
+    assert_eq!(new.index(), old.index());
+
+    let stale = Err(InputError::StaleHandle);
+    assert_eq!(map.set_bindings(old, vec!["C"]), stale);
+    assert_eq!(map.append_binding(old, "C"), stale);
+    assert_eq!(map.remove_binding(old, &Binding::Single("B")), stale);
+    assert_eq!(map.set_response(old, "B", AxisResponse::new()), stale);
+    assert_eq!(map.clear_response(old, &"B"), stale);
+    assert!(map.bindings(old).is_err());
+    assert!(map.get_action_by_handle(old).is_err());
+    assert!(map.user(user).unwrap().held_for(old).is_err());
+    assert!(map.user(user).unwrap().double_tapped(old).is_err());
+    assert!(map.user(user).unwrap().get_key_by_handle(old).is_err());
+    assert!(map
+        .user_mut(user)
+        .unwrap()
+        .update_action(old, digital())
+        .is_err());
+    assert_eq!(map.remove_action(old), stale);
+
+    //The new action kept its own binding
+    assert_eq!(map.bindings(new).unwrap(), vec![&Binding::Single("B")]);
+    assert_eq!(
+        map.user(user).unwrap().get_key_by_handle(new).unwrap(),
+        "New"
+    );
+}
+
+#[test]
+fn handles_only_fit_their_user() {
+    let mut map = InputMap::new();
+    let first = map.add_user();
+    let second = map.add_user();
+    let jump = map
+        .add_action(first, "Jump".to_string(), vec!["A"], digital())
+        .unwrap();
+
+    assert_eq!(
+        map.user(second).unwrap().held_for(jump),
+        Err(InputError::HandleUserMismatch)
+    );
+}
+
+#[test]
+fn removed_users_make_their_handles_stale() {
+    let mut map = InputMap::new();
+    let old = map.add_user();
+    let jump = map
+        .add_action(old, "Jump".to_string(), vec!["A"], digital())
+        .unwrap();
+
+    map.remove_user(old).unwrap();
+    assert_eq!(map.remove_user(old), Err(InputError::StaleHandle));
+    assert!(!map.has_user(old));
+
+    //The new user takes the slot, but the old handle does not reach it
+    let new = map.add_user();
+    assert_eq!(new.index(), old.index());
+    assert_ne!(new, old);
+
+    let stale = Err(InputError::StaleHandle);
+    assert_eq!(map.claim_device(old, DeviceId(1)), stale);
+    assert_eq!(map.rumble(old, Rumble::new(1.0, 1.0, 1.0)), stale);
+    assert_eq!(map.stop_rumble(old), stale);
+    assert_eq!(map.motors(old), Err(InputError::StaleHandle));
+    assert_eq!(map.user_devices(old), Err(InputError::StaleHandle));
+    assert!(map.user(old).is_err());
+    assert!(map
+        .add_action(old, "Fire".to_string(), vec!["B"], digital())
+        .is_err());
+    assert!(map.get_action_by_handle(jump).is_err());
+    assert!(map.bindings(jump).is_err());
+
+    map.claim_device(new, DeviceId(1)).unwrap();
+    assert_eq!(map.device_owner(DeviceId(1)), Some(new));
+    assert!(map.user(new).unwrap().is_empty());
+}
+
+#[test]
+fn sequences_of_removed_actions_never_match() {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    let a = map
+        .add_action(user, "A".to_string(), vec!["A"], digital())
+        .unwrap();
+    let b = map
+        .add_action(user, "B".to_string(), vec!["B"], digital())
+        .unwrap();
+    let sequence = map
+        .user_mut(user)
+        .unwrap()
+        .add_sequence(Sequence::new(vec![vec![a], vec![b]], 30, 10))
+        .unwrap();
+
+    map.process(&"A", InputValue::Digital(true)).unwrap();
+    map.advance(0.016);
+    map.process(&"A", InputValue::Digital(false)).unwrap();
+    map.advance(0.016);
+
+    map.remove_action(b).unwrap();
+    let c = map
+        .add_action(user, "C".to_string(), vec!["C"], digital())
+        .unwrap();
+    assert_eq!(c.index(), b.index());
+
+    map.process(&"C", InputValue::Digital(true)).unwrap();
+    map.advance(0.016);
+    assert!(!map.user(user).unwrap().sequence_matched(sequence).unwrap());
+
+    assert_eq!(
+        map.user_mut(user)
+            .unwrap()
+            .add_sequence(Sequence::new(vec![vec![a], vec![b]], 30, 10)),
+        Err(InputError::StaleHandle)
+    );
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::Map;
+use stagehand::input::{
+    haptics::{Motors, Rumble},
+    UserHandle,
+};
+
+const STEP: f64 = 0.125;
+
+fn low_motor_over(map: &mut Map, user: UserHandle, steps: usize) -> Vec<f32> {
+    (0..steps)
+        .map(|_| {
+            map.advance(STEP);
+            map.motors(user).unwrap().low
+        })
+        .collect()
+}
+
+#[test]
+fn envelopes_ramp_up_and_down() {
+    let mut map = Map::new();
+    let user = map.add_user();
+
+    map.rumble(user, Rumble::new(1.0, 0.5, 1.0).with_envelope(0.25, 0.5))
+        .unwrap();
+    assert_eq!(map.motors(user).unwrap(), Motors::default());
+
+    assert_eq!(
+        low_motor_over(&mut map, user, 9),
+        vec![0.0, 0.5, 1.0, 1.0, 1.0, 0.75, 0.5, 0.25, 0.0]
+    );
+}
+
+#[test]
+fn both_motors_follow_the_envelope_and_intensity() {
+    let mut map = Map::new();
+    let user = map.add_user();
+    map.set_haptic_intensity(0.5);
+
+    map.rumble(user, Rumble::new(1.0, 0.5, 1.0).with_envelope(0.25, 0.0))
+        .unwrap();
+    map.advance(STEP);
+    map.advance(STEP);
+
+    assert_eq!(
+        map.motors(user).unwrap(),
+        Motors {
+            low: 0.25,
+            high: 0.125
+        }
+    );
+}
+
+#[test]
+fn stopping_takes_effect_on_the_next_advance() {
+    let mut map = Map::new();
+    let user = map.add_user();
+
+    map.rumble(user, Rumble::new(0.8, 0.4, f64::INFINITY))
+        .unwrap();
+    assert_eq!(low_motor_over(&mut map, user, 3), vec![0.8; 3]);
+
+    //Motors still report what the host was told for this update
+    map.stop_rumble(user).unwrap();
+    assert_eq!(map.motors(user).unwrap().low, 0.8);
+
+    map.advance(STEP);
+    assert_eq!(map.motors(user).unwrap(), Motors::default());
+}
+
+#[test]
+fn overlapping_rumbles_take_the_strongest_motor() {
+    let mut map = Map::new();
+    let user = map.add_user();
+
+    map.rumble(user, Rumble::new(0.2, 0.9, 0.25)).unwrap();
+    map.rumble(user, Rumble::new(0.6, 0.1, 0.5)).unwrap();
+
+    map.advance(STEP);
+    assert_eq!(
+        map.motors(user).unwrap(),
+        Motors {
+            low: 0.6,
+            high: 0.9
+        }
+    );
+
+    //The shorter one is over after two steps
+    map.advance(STEP);
+    map.advance(STEP);
+    assert_eq!(
+        map.motors(user).unwrap(),
+        Motors {
+            low: 0.6,
+            high: 0.1
+        }
+    );
+}
@@ -0,0 +1,384 @@
+#![cfg(feature = "loading")]
+
+use std::{thread, time::Duration};
+
+use stagehand::loading::{
+    background::{AsyncResourceLoader, BackgroundLoader, LoadProgress},
+    registry::ResourceRegistry,
+    resources::{ResourceLoadError, ResourceLoader, ResourceStorage},
+    ResourceError, Ticket, TicketManager,
+};
+
+struct Loader;
+
+impl<'a> ResourceLoader<'a, String> for Loader {
+    type Arguments = str;
+
+    fn load(&'a self, args: &str) -> Result<String, ResourceLoadError> {
+        Ok(args.to_string())
+    }
+}
+
+//Fails on empty text, counting a byte per char otherwise
+struct Slow;
+
+impl AsyncResourceLoader<String> for Slow {
+    type Arguments = String;
+    type Data = String;
+
+    fn load(&self, args: &String) -> Result<String, ResourceLoadError> {
+        thread::sleep(Duration::from_millis(20));
+
+        match args.is_empty() {
+            true => Err(ResourceLoadError::LoadFailure("empty".to_string())),
+            false => Ok(args.clone()),
+        }
+    }
+
+    fn finish(&self, data: String) -> Result<String, ResourceLoadError> {
+        Ok(data)
+    }
+
+    fn bytes(&self, data: &String) -> u64 {
+        data.len() as u64
+    }
+}
+
+fn wait(storage: &mut ResourceStorage<'_, String, String, Loader>) {
+    while !storage.is_loading_complete() {
+        assert!(storage.poll().is_empty());
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn storage(loader: &Loader) -> ResourceStorage<'_, String, String, Loader> {
+    let mut storage = ResourceStorage::new(loader);
+    storage.load("a".to_string(), "first").unwrap();
+    storage.load("b".to_string(), "second").unwrap();
+
+    storage
+}
+
+#[test]
+fn unloaded_tickets_are_outdated() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+
+    let ticket = storage.take_ticket("a").unwrap();
+    assert_eq!(*storage.get_by_ticket(&ticket).unwrap().borrow(), "first");
+
+    storage.unload("a").unwrap();
+
+    assert!(matches!(
+        storage.get_by_ticket(&ticket),
+        Err(ResourceError::TicketOutdated)
+    ));
+    assert!(matches!(
+        storage.get_by_key("a"),
+        Err(ResourceError::NotStored(_))
+    ));
+    assert!(matches!(
+        storage.unload("a"),
+        Err(ResourceError::NotStored(_))
+    ));
+}
+
+#[test]
+fn reused_slots_do_not_revive_old_tickets() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+
+    let old = storage.take_ticket("a").unwrap();
+    storage.unload("a").unwrap();
+    storage.load("c".to_string(), "third").unwrap();
+
+    let new = storage.take_ticket("c").unwrap();
+    assert_eq!(*storage.get_by_ticket(&new).unwrap().borrow(), "third");
+    assert!(matches!(
+        storage.get_by_ticket(&old),
+        Err(ResourceError::TicketOutdated)
+    ));
+}
+
+#[test]
+fn unloading_leaves_other_tickets_alone() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+
+    let ticket = storage.take_ticket("b").unwrap();
+    storage.unload("a").unwrap();
+    storage.load("d".to_string(), "fourth").unwrap();
+
+    assert_eq!(*storage.get_by_ticket(&ticket).unwrap().borrow(), "second");
+}
+
+#[test]
+fn replace_outdates_tickets_and_reload_keeps_them() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+
+    assert!(matches!(
+        storage.load("a".to_string(), "again"),
+        Err(ResourceLoadError::AlreadyExists(_))
+    ));
+
+    let ticket = storage.take_ticket("a").unwrap();
+    storage.reload(&"a".to_string(), "reloaded").unwrap();
+    assert_eq!(
+        *storage.get_by_ticket(&ticket).unwrap().borrow(),
+        "reloaded"
+    );
+
+    let handle = storage.get_by_key("a").unwrap();
+    storage.replace("a".to_string(), "replaced").unwrap();
+    assert!(matches!(
+        storage.get_by_ticket(&ticket),
+        Err(ResourceError::TicketOutdated)
+    ));
+    assert_eq!(*handle.borrow(), "reloaded");
+
+    let ticket = storage.take_ticket("a").unwrap();
+    assert_eq!(
+        *storage.get_by_ticket(&ticket).unwrap().borrow(),
+        "replaced"
+    );
+}
+
+#[test]
+fn auto_unload_waits_for_tickets_and_handles() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+    storage.set_auto_unload(true);
+
+    let ticket = storage.take_ticket("a").unwrap();
+    let handle = storage.get_by_key("b").unwrap();
+    storage.poll();
+    assert!(storage.contains_key("a"));
+    assert!(storage.contains_key("b"));
+
+    let erased = ticket.erase();
+    drop(ticket);
+    drop(handle);
+    storage.poll();
+
+    assert!(!storage.contains_key("a"));
+    assert!(!storage.contains_key("b"));
+    assert!(matches!(
+        storage.check_any_ticket(erased),
+        Err(ResourceError::TicketOutdated)
+    ));
+}
+
+#[test]
+fn auto_unload_waits_for_every_ticket_copy() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+    storage.set_auto_unload(true);
+
+    let ticket = storage.take_ticket("a").unwrap();
+    let copy = ticket.clone();
+    drop(copy.clone());
+    drop(copy);
+    storage.poll();
+
+    assert!(storage.contains_key("a"));
+    assert_eq!(*storage.get_by_ticket(&ticket).unwrap().borrow(), "first");
+
+    drop(ticket);
+    storage.poll();
+    assert!(!storage.contains_key("a"));
+}
+
+#[test]
+fn locked_storages_do_not_change() {
+    let loader = Loader;
+    let mut storage = storage(&loader);
+    let ticket = storage.take_ticket("a").unwrap();
+
+    storage.lock();
+    assert!(matches!(
+        storage.unload("a"),
+        Err(ResourceError::StorageLocked)
+    ));
+    assert!(matches!(
+        storage.load("c".to_string(), "third"),
+        Err(ResourceLoadError::StorageLocked)
+    ));
+
+    storage.unlock();
+    storage.unload("a").unwrap();
+    assert!(storage.get_by_ticket(&ticket).is_err());
+}
+
+#[test]
+fn replace_cancels_pending_loads() {
+    let loader = Loader;
+    let background = BackgroundLoader::new(Slow, 1);
+    let mut storage = storage(&loader);
+
+    storage
+        .load_async(&background, "c".to_string(), "late".to_string())
+        .unwrap();
+    storage.replace("c".to_string(), "replaced").unwrap();
+    let ticket = storage.take_ticket("c").unwrap();
+    wait(&mut storage);
+
+    assert_eq!(
+        *storage.get_by_ticket(&ticket).unwrap().borrow(),
+        "replaced"
+    );
+    assert_eq!(*storage.get_by_key("c").unwrap().borrow(), "replaced");
+}
+
+#[test]
+fn background_progress_counts_loads_failures_and_bytes() {
+    let loader = Loader;
+    let background = BackgroundLoader::new(Slow, 2);
+    let mut storage = storage(&loader);
+    assert!(storage.is_loading_complete());
+    assert_eq!(storage.progress().fraction(), 1.0);
+
+    for (key, text) in [("c", "late"), ("d", "later"), ("e", "")] {
+        storage
+            .load_async(&background, key.to_string(), text.to_string())
+            .unwrap();
+    }
+    assert_eq!(
+        storage.progress(),
+        LoadProgress {
+            done: 0,
+            failed: 0,
+            total: 3,
+            bytes: 0,
+        }
+    );
+    assert_eq!(storage.progress().fraction(), 0.0);
+
+    let mut failures = Vec::new();
+    while !storage.is_loading_complete() {
+        failures.extend(storage.poll());
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    assert!(matches!(
+        failures.as_slice(),
+        [ResourceLoadError::LoadFailure(_)]
+    ));
+    assert_eq!(
+        storage.progress(),
+        LoadProgress {
+            done: 3,
+            failed: 1,
+            total: 3,
+            bytes: 9,
+        }
+    );
+    assert!(storage.contains_key("c") && storage.contains_key("d"));
+    assert!(!storage.contains_key("e"));
+
+    //The next request starts a fresh batch
+    storage
+        .load_async(&background, "f".to_string(), "next".to_string())
+        .unwrap();
+    assert_eq!(
+        storage.progress(),
+        LoadProgress {
+            done: 0,
+            failed: 0,
+            total: 1,
+            bytes: 0,
+        }
+    );
+}
+
+#[test]
+fn unload_cancels_pending_loads() {
+    let loader = Loader;
+    let background = BackgroundLoader::new(Slow, 1);
+    let mut storage = storage(&loader);
+
+    storage
+        .load_async(&background, "c".to_string(), "late".to_string())
+        .unwrap();
+    storage.unload("c").unwrap();
+    wait(&mut storage);
+
+    assert!(!storage.contains_key("c"));
+    assert!(matches!(
+        storage.unload("c"),
+        Err(ResourceError::NotStored(_))
+    ));
+
+    storage
+        .load_async(&background, "c".to_string(), "again".to_string())
+        .unwrap();
+    wait(&mut storage);
+    assert_eq!(*storage.get_by_key("c").unwrap().borrow(), "again");
+}
+
+//Scenes see the registry through the trait, like the examples do
+fn ticket_from<R: 'static, M: TicketManager<String, str, String, str>>(
+    manager: &M,
+    storage: &str,
+    key: &str,
+) -> Result<Ticket<R>, ResourceError> {
+    manager.get_ticket_with_key(storage, key)
+}
+
+#[test]
+fn registry_tickets_are_typed_by_storage() {
+    let loader = Loader;
+    let mut registry: ResourceRegistry<String, str> = ResourceRegistry::new();
+    let storage = registry
+        .add_storage("Text".to_string(), storage(&loader))
+        .unwrap();
+
+    let ticket: Ticket<String> = ticket_from(&registry, "Text", "a").unwrap();
+    assert_eq!(
+        *storage.borrow().get_by_ticket(&ticket).unwrap().borrow(),
+        "first"
+    );
+
+    assert!(matches!(
+        ticket_from::<u32, _>(&registry, "Text", "a"),
+        Err(ResourceError::WrongStorage)
+    ));
+    assert!(matches!(
+        ticket_from::<String, _>(&registry, "Sound", "a"),
+        Err(ResourceError::UnknownStorage(_))
+    ));
+}
+
+#[test]
+fn registry_tickets_fail_while_the_storage_is_borrowed() {
+    let loader = Loader;
+    let mut registry: ResourceRegistry<String, str> = ResourceRegistry::new();
+    let storage = registry
+        .add_storage("Text".to_string(), storage(&loader))
+        .unwrap();
+
+    let loading = storage.borrow_mut();
+    assert!(matches!(
+        ticket_from::<String, _>(&registry, "Text", "a"),
+        Err(ResourceError::StorageBorrowed(_))
+    ));
+
+    drop(loading);
+    assert!(ticket_from::<String, _>(&registry, "Text", "a").is_ok());
+}
+
+#[test]
+fn erased_tickets_only_fit_their_storage() {
+    let loader = Loader;
+    let first = storage(&loader);
+    let second = storage(&loader);
+
+    let ticket = first.take_ticket("a").unwrap().erase();
+    let typed = first.check_any_ticket(ticket).unwrap();
+    assert_eq!(*first.get_by_ticket(&typed).unwrap().borrow(), "first");
+
+    assert!(matches!(
+        second.check_any_ticket(ticket),
+        Err(ResourceError::WrongStorage)
+    ));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use stagehand::input::{
+    pointer::Pointer, ActionHandle, ActionState, ActionType, InputMap, InputValue, UserHandle,
+};
+
+fn pointer(map: &InputMap<&'static str>, user: UserHandle) -> Pointer {
+    match map.user(user).unwrap().get_action_by_key("Aim").unwrap() {
+        ActionType::Pointer(pointer) => pointer,
+        _ => panic!("Aim should stay a pointer"),
+    }
+}
+
+fn axis(map: &InputMap<&'static str>, handle: ActionHandle) -> f32 {
+    match map.get_action_by_handle(handle).unwrap() {
+        ActionType::Axis(value) => value,
+        _ => panic!("Scroll should stay an axis"),
+    }
+}
+
+#[test]
+fn first_position_does_not_move() {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    map.add_action(
+        user,
+        "Aim".to_string(),
+        vec!["Mouse"],
+        ActionType::Pointer(Pointer::default()),
+    )
+    .unwrap();
+
+    map.process(&"Mouse", InputValue::PointerPosition { x: 500.0, y: 400.0 })
+        .unwrap();
+    map.advance(0.0);
+
+    let first = pointer(&map, user);
+    assert_eq!((first.x, first.y), (500.0, 400.0));
+    assert!(!first.moved());
+
+    map.process(&"Mouse", InputValue::PointerPosition { x: 510.0, y: 395.0 })
+        .unwrap();
+    map.advance(0.0);
+
+    let second = pointer(&map, user);
+    assert_eq!((second.dx, second.dy), (10.0, -5.0));
+
+    map.advance(0.0);
+    assert!(!pointer(&map, user).moved());
+}
+
+#[test]
+fn wheel_ticks_never_hold_digital_actions() {
+    let (mut map, user, actions) = common::map(&["Wheel"]);
+    let scroll = map
+        .add_action(
+            user,
+            "Scroll".to_string(),
+            vec!["Wheel"],
+            ActionType::Axis(0.0),
+        )
+        .unwrap();
+
+    map.capture_next();
+    map.process(&"Wheel", InputValue::PointerWheel(1.0))
+        .unwrap();
+    assert!(map.is_capturing());
+    map.cancel_capture();
+
+    map.process(&"Wheel", InputValue::PointerWheel(1.0))
+        .unwrap();
+    map.advance(0.0);
+    assert!(common::state(&map, actions[0]) == ActionState::Up);
+    assert!(axis(&map, scroll) == 1.0);
+
+    //The tick lasts for one update, then the wheel settles by itself
+    map.advance(0.0);
+    assert!(common::state(&map, actions[0]) == ActionState::Up);
+    assert!(axis(&map, scroll) == 0.0);
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{map, set, state, Map};
+use stagehand::input::{
+    recording::{RecordedInput, Recording},
+    ActionHandle, ActionState, InputValue,
+};
+
+fn states(map: &mut Map, handle: ActionHandle, advances: usize) -> Vec<ActionState> {
+    (0..advances)
+        .map(|_| {
+            map.advance(0.016);
+            state(map, handle)
+        })
+        .collect()
+}
+
+fn press(tick: u64, input: &'static str, down: bool) -> RecordedInput<&'static str> {
+    RecordedInput {
+        tick,
+        device: None,
+        input,
+        value: InputValue::Digital(down),
+    }
+}
+
+//Space held for two updates, then let go
+fn record() -> (Recording<&'static str>, Vec<ActionState>) {
+    let (mut map, _, actions) = map(&["Space"]);
+    map.start_recording();
+
+    set(&mut map, "Space", true);
+    let mut seen = states(&mut map, actions[0], 2);
+    set(&mut map, "Space", false);
+    seen.extend(states(&mut map, actions[0], 2));
+
+    (map.stop_recording().unwrap(), seen)
+}
+
+#[test]
+fn recordings_survive_saving_as_text() {
+    let (recording, _) = record();
+    assert_eq!(
+        recording.inputs,
+        vec![press(0, "Space", true), press(2, "Space", false)]
+    );
+
+    let text = recording.to_string();
+    assert_eq!(text, "stagehand-recording 1\n0 - d1 Space\n2 - d0 Space\n");
+
+    let parsed = Recording::<String>::parse(&text).unwrap();
+    let inputs: Vec<_> = parsed
+        .inputs
+        .iter()
+        .map(|r| (r.tick, r.device, r.input.as_str(), r.value))
+        .collect();
+    assert_eq!(
+        inputs,
+        vec![
+            (0, None, "Space", InputValue::Digital(true)),
+            (2, None, "Space", InputValue::Digital(false)),
+        ]
+    );
+}
+
+#[test]
+fn playback_repeats_what_was_recorded() {
+    let (recording, recorded) = record();
+    let (mut map, _, actions) = map(&["Space"]);
+
+    map.play(recording, false).unwrap();
+    assert!(states(&mut map, actions[0], 4) == recorded);
+    assert!(!map.is_playing());
+}
+
+#[test]
+fn live_presses_interrupt_and_let_go_of_played_inputs() {
+    let (mut map, _, actions) = map(&["Space", "Enter"]);
+    let recording = Recording {
+        inputs: vec![press(0, "Space", true), press(10, "Space", false)],
+    };
+
+    map.play(recording, true).unwrap();
+    assert!(states(&mut map, actions[0], 2) == vec![ActionState::Pressed, ActionState::Down]);
+
+    set(&mut map, "Enter", true);
+    assert!(!map.is_playing());
+    assert!(states(&mut map, actions[0], 1) == vec![ActionState::Released]);
+    assert!(state(&map, actions[1]) == ActionState::Pressed);
+}
+
+#[test]
+fn uninterruptible_playback_keeps_going() {
+    let (mut map, _, actions) = map(&["Space", "Enter"]);
+    let recording = Recording {
+        inputs: vec![press(0, "Space", true), press(3, "Space", false)],
+    };
+
+    map.play(recording, false).unwrap();
+    map.advance(0.016);
+
+    set(&mut map, "Enter", true);
+    assert!(map.is_playing());
+    assert!(
+        states(&mut map, actions[0], 3)
+            == vec![ActionState::Down, ActionState::Down, ActionState::Released]
+    );
+    assert!(!map.is_playing());
+}
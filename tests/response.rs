@@ -0,0 +1,122 @@
+#![cfg(feature = "input")]
+
+use stagehand::input::{
+    response::{AxisResponse, Curve, Deadzone},
+    ActionType, InputMap, InputValue,
+};
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+#[test]
+fn linear_passes_values_through_and_saturates() {
+    let response = AxisResponse::new();
+
+    assert!(close(response.axis(0.5), 0.5));
+    assert!(close(response.axis(-0.25), -0.25));
+    assert!(close(response.axis(1.5), 1.0));
+    assert!(close(response.axis(0.0), 0.0));
+}
+
+#[test]
+fn axial_deadzone_rescales_the_remaining_range() {
+    let response = AxisResponse::new().with_deadzone(Deadzone::Axial(0.2));
+
+    assert!(close(response.axis(0.1), 0.0));
+    assert!(close(response.axis(0.2), 0.0));
+    assert!(close(response.axis(0.6), 0.5));
+    assert!(close(response.axis(-0.6), -0.5));
+
+    let (x, y) = response.analog(0.1, 0.6);
+    assert!(close(x, 0.0));
+    assert!(close(y, 0.5));
+}
+
+#[test]
+fn radial_deadzone_keeps_direction() {
+    let response = AxisResponse::new().with_deadzone(Deadzone::Radial(0.2));
+
+    let (x, y) = response.analog(0.3, 0.4);
+    assert!(close(x, 0.225));
+    assert!(close(y, 0.3));
+
+    let (x, y) = response.analog(0.1, 0.15);
+    assert!(close(x, 0.0));
+    assert!(close(y, 0.0));
+
+    //Past the deadzone in total, so the small axis survives unlike with axial
+    let (x, _) = response.analog(0.1, 0.6);
+    assert!(x > 0.0);
+}
+
+#[test]
+fn saturation_reaches_full_early() {
+    let response = AxisResponse::new()
+        .with_deadzone(Deadzone::Axial(0.1))
+        .with_saturation(0.9);
+
+    assert!(close(response.axis(0.5), 0.5));
+    assert!(close(response.axis(0.95), 1.0));
+}
+
+#[test]
+fn curves() {
+    let quadratic = AxisResponse::new().with_curve(Curve::Quadratic);
+    let cubic = AxisResponse::new().with_curve(Curve::Cubic);
+    let custom = AxisResponse::new().with_curve(Curve::Custom(f32::sqrt));
+
+    assert!(close(quadratic.axis(0.5), 0.25));
+    assert!(close(quadratic.axis(-0.5), -0.25));
+    assert!(close(cubic.axis(0.5), 0.125));
+    assert!(close(custom.axis(0.25), 0.5));
+}
+
+#[test]
+fn sensitivity_and_inversion() {
+    let response = AxisResponse::new()
+        .with_sensitivity(2.0)
+        .with_inversion(false, true);
+
+    assert!(close(response.axis(0.25), 0.5));
+
+    let (x, y) = response.analog(0.25, 0.25);
+    assert!(close(x, 0.5));
+    assert!(close(y, -0.5));
+}
+
+#[test]
+fn responses_apply_while_processing() {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    let look = map
+        .add_action(
+            user,
+            "Look".to_string(),
+            vec!["Stick"],
+            ActionType::Analog { x: 0.0, y: 0.0 },
+        )
+        .unwrap();
+    map.set_response(
+        look,
+        "Stick",
+        AxisResponse::new().with_deadzone(Deadzone::Radial(0.2)),
+    )
+    .unwrap();
+
+    map.process(&"Stick", InputValue::Analog { x: 0.1, y: 0.0 })
+        .unwrap();
+    map.advance(0.0);
+    match map.user(user).unwrap().get_action_by_key("Look").unwrap() {
+        ActionType::Analog { x, y } => assert!(close(x, 0.0) && close(y, 0.0)),
+        _ => panic!("Look should stay analog"),
+    }
+
+    map.process(&"Stick", InputValue::Analog { x: 0.0, y: 0.6 })
+        .unwrap();
+    map.advance(0.0);
+    match map.user(user).unwrap().get_action_by_key("Look").unwrap() {
+        ActionType::Analog { x, y } => assert!(close(x, 0.0) && close(y, 0.5)),
+        _ => panic!("Look should stay analog"),
+    }
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{set, Map};
+use stagehand::input::{sequence::Sequence, ActionHandle, UserHandle};
+
+fn map() -> (Map, UserHandle) {
+    let (map, user, _) = common::map(&["A", "B", "C"]);
+
+    (map, user)
+}
+
+fn handle(map: &Map, user: UserHandle, key: &str) -> ActionHandle {
+    map.user(user).unwrap().get_handle_by_key(key).unwrap()
+}
+
+//Sets the input and advances once, returning whether sequence 0 matched
+fn step(map: &mut Map, user: UserHandle, input: &'static str, down: bool) -> bool {
+    set(map, input, down);
+    map.advance(0.016);
+
+    map.user(user).unwrap().sequence_matched(0).unwrap()
+}
+
+fn a_then_b(map: &mut Map, user: UserHandle) {
+    let (a, b) = (handle(map, user, "A"), handle(map, user, "B"));
+
+    map.user_mut(user)
+        .unwrap()
+        .add_sequence(Sequence::new(vec![vec![a], vec![b]], 30, 10))
+        .unwrap();
+}
+
+#[test]
+fn fires_once_when_completed() {
+    let (mut map, user) = map();
+    a_then_b(&mut map, user);
+
+    assert!(!step(&mut map, user, "A", true));
+    assert!(!step(&mut map, user, "A", false));
+    assert!(step(&mut map, user, "B", true));
+
+    map.advance(0.016);
+    assert!(!map.user(user).unwrap().sequence_matched(0).unwrap());
+}
+
+#[test]
+fn unwatched_actions_do_not_fire_it_again() {
+    let (mut map, user) = map();
+    a_then_b(&mut map, user);
+
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "A", false);
+    assert!(step(&mut map, user, "B", true));
+
+    //B is still held, which is where the last step left off
+    assert!(!step(&mut map, user, "C", true));
+    assert!(!step(&mut map, user, "C", false));
+}
+
+#[test]
+fn completing_it_again_fires_again() {
+    let (mut map, user) = map();
+    a_then_b(&mut map, user);
+
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "A", false);
+    assert!(step(&mut map, user, "B", true));
+    assert!(!step(&mut map, user, "B", false));
+
+    //The release of B can't stand in for the press of A
+    assert!(!step(&mut map, user, "B", true));
+    step(&mut map, user, "B", false);
+
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "A", false);
+    assert!(step(&mut map, user, "B", true));
+}
+
+#[test]
+fn too_slow_steps_do_not_match() {
+    let (mut map, user) = map();
+    a_then_b(&mut map, user);
+
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "A", false);
+    for _ in 0..15 {
+        map.advance(0.016);
+    }
+
+    assert!(!step(&mut map, user, "B", true));
+}
+
+#[test]
+fn extra_watched_actions_break_a_step() {
+    let (mut map, user) = map();
+    let (a, b, c) = (
+        handle(&map, user, "A"),
+        handle(&map, user, "B"),
+        handle(&map, user, "C"),
+    );
+    map.user_mut(user)
+        .unwrap()
+        .add_sequence(Sequence::new(vec![vec![a], vec![b], vec![c]], 30, 10))
+        .unwrap();
+
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "A", false);
+    //A is watched, so B pressed only while A is held is never the B step
+    step(&mut map, user, "A", true);
+    step(&mut map, user, "B", true);
+    step(&mut map, user, "B", false);
+    step(&mut map, user, "A", false);
+
+    assert!(!step(&mut map, user, "C", true));
+}
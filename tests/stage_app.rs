@@ -0,0 +1,113 @@
+#![cfg(feature = "2d")]
+
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use stagehand::{
+    app::{gameloop, Lifecycle},
+    clock::ManualClock,
+    input::InputMap,
+    scene::Scene,
+    utility::{
+        stage_app::{EventBackend, StageApp},
+        Initialize, Update,
+    },
+    Response, Stage,
+};
+
+type Map = InputMap<&'static str>;
+
+//Writes down everything the stage hands it
+struct Recorder {
+    seen: Rc<RefCell<Vec<String>>>,
+}
+
+impl Scene for Recorder {
+    type Key = &'static str;
+    type Initialize = Initialize<&'static str, (), ()>;
+    type Update = Update<&'static str, ()>;
+    type Message = ();
+    type Instruction = ();
+    type Draw = ();
+    type DrawBatch = ();
+
+    fn initialize(&mut self, _init: &mut Self::Initialize) {}
+
+    fn update(
+        &mut self,
+        update: &Self::Update,
+        _delta: f64,
+    ) -> Vec<Response<&'static str, (), ()>> {
+        let info = update.info.borrow().len();
+        self.seen
+            .borrow_mut()
+            .push(format!("update with {} info", info));
+
+        Vec::new()
+    }
+
+    fn draw(&self, _draw: &(), _interp: f64) {}
+
+    fn receive_message(&mut self, _message: &()) {}
+
+    fn covering(&self) -> bool {
+        true
+    }
+
+    fn blocking(&self) -> bool {
+        true
+    }
+
+    fn lifecycle(&mut self, event: Lifecycle) {
+        self.seen.borrow_mut().push(format!("{:?}", event));
+    }
+}
+
+//Moves the clock a step per poll, suspends on the second and resumes on the fourth
+struct Script {
+    clock: Rc<ManualClock>,
+    polls: u32,
+}
+
+impl EventBackend<&'static str> for Script {
+    type Error = Infallible;
+
+    fn poll(&mut self, _input: &mut Map) -> Result<bool, Infallible> {
+        self.polls += 1;
+        self.clock.advance(100);
+
+        Ok(self.polls <= 6)
+    }
+
+    fn lifecycle(&mut self) -> Vec<Lifecycle> {
+        match self.polls {
+            2 => vec![Lifecycle::Suspended],
+            4 => vec![Lifecycle::Resumed],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn scenes_hear_about_suspending_before_the_next_update() {
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let mut stage = Stage::new();
+    stage.add_scene("Game", Box::new(Recorder { seen: seen.clone() }), true);
+
+    let clock = Rc::new(ManualClock::new(0));
+    let mut app = StageApp::new(stage, (), Map::new(), (), ())
+        .with_clock(clock.clone())
+        .with_events(Script { clock, polls: 0 });
+
+    gameloop(&mut app, 10).unwrap();
+
+    //The time spent suspended is dropped, so only the two steps after resuming update
+    assert_eq!(
+        *seen.borrow(),
+        vec![
+            "Suspended",
+            "Resumed",
+            "update with 0 info",
+            "update with 0 info",
+        ]
+    );
+}
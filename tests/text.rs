@@ -0,0 +1,118 @@
+#![cfg(feature = "input")]
+
+use stagehand::input::text::{EditKey, LineBuffer, TextEvent, TextInput};
+
+fn edit(buffer: &mut LineBuffer, keys: &[EditKey]) {
+    for key in keys {
+        buffer.edit(*key, false);
+    }
+}
+
+#[test]
+fn inserts_and_backspaces_at_the_cursor() {
+    let mut buffer = LineBuffer::new();
+
+    buffer.insert("helo");
+    edit(&mut buffer, &[EditKey::Left]);
+    buffer.insert("l");
+    assert_eq!(buffer.text(), "hello");
+    assert_eq!(buffer.cursor(), 4);
+
+    edit(&mut buffer, &[EditKey::End, EditKey::Backspace]);
+    assert_eq!(buffer.text(), "hell");
+    assert_eq!(buffer.cursor(), 4);
+
+    edit(&mut buffer, &[EditKey::Home, EditKey::Delete]);
+    assert_eq!(buffer.text(), "ell");
+    assert_eq!(buffer.cursor(), 0);
+}
+
+#[test]
+fn edits_stop_at_the_ends() {
+    let mut buffer = LineBuffer::new().with_text("ab");
+
+    edit(&mut buffer, &[EditKey::Right, EditKey::Delete]);
+    assert_eq!((buffer.text(), buffer.cursor()), ("ab", 2));
+
+    edit(
+        &mut buffer,
+        &[EditKey::Home, EditKey::Left, EditKey::Backspace],
+    );
+    assert_eq!((buffer.text(), buffer.cursor()), ("ab", 0));
+
+    let mut empty = LineBuffer::new();
+    edit(
+        &mut empty,
+        &[EditKey::Backspace, EditKey::Delete, EditKey::Right],
+    );
+    assert_eq!((empty.text(), empty.cursor()), ("", 0));
+}
+
+#[test]
+fn the_cursor_steps_over_whole_chars() {
+    let mut buffer = LineBuffer::new().with_text("né");
+    assert_eq!(buffer.cursor(), 3);
+
+    edit(&mut buffer, &[EditKey::Left]);
+    assert_eq!(buffer.cursor(), 1);
+
+    edit(&mut buffer, &[EditKey::End, EditKey::Backspace]);
+    assert_eq!((buffer.text(), buffer.cursor()), ("n", 1));
+}
+
+#[test]
+fn typing_replaces_the_selection() {
+    let mut buffer = LineBuffer::new().with_text("hello");
+
+    buffer.edit(EditKey::Left, true);
+    buffer.edit(EditKey::Left, true);
+    assert_eq!(buffer.selected_text(), "lo");
+
+    buffer.insert("p!");
+    assert_eq!(buffer.text(), "help!");
+    assert_eq!(buffer.selection(), None);
+
+    //Moving without select collapses to the edge of the selection
+    buffer.select_all();
+    edit(&mut buffer, &[EditKey::Left]);
+    assert_eq!((buffer.cursor(), buffer.selection()), (0, None));
+}
+
+#[test]
+fn inserts_are_cut_at_the_max_length() {
+    let mut buffer = LineBuffer::new().with_max_len(4);
+
+    buffer.insert("ab");
+    buffer.insert("cdef");
+    assert_eq!(buffer.text(), "abcd");
+
+    buffer.insert("g");
+    assert_eq!(buffer.text(), "abcd");
+}
+
+#[test]
+fn apply_reads_the_events_of_the_update() {
+    let mut input = TextInput::new();
+    let mut buffer = LineBuffer::new();
+    input.enable();
+
+    input.push(TextEvent::Commit("hi".to_string()));
+    input.push(TextEvent::Edit {
+        key: EditKey::Backspace,
+        select: false,
+    });
+    assert!(!buffer.apply(&input));
+    assert_eq!(buffer.text(), "");
+
+    input.advance();
+    input.push(TextEvent::Edit {
+        key: EditKey::Enter,
+        select: false,
+    });
+    assert!(!buffer.apply(&input));
+    assert_eq!(buffer.text(), "h");
+
+    input.advance();
+    assert!(buffer.apply(&input));
+    assert_eq!(buffer.text(), "h");
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "input")]
+
+mod common;
+
+use common::{set, Map};
+use stagehand::input::{timing::GestureConfig, ActionHandle};
+
+//Steps of an eighth of a second add up without rounding
+const STEP: f64 = 0.125;
+
+fn map() -> (Map, ActionHandle) {
+    let (mut map, user, actions) = common::map(&["Space"]);
+    map.user_mut(user).unwrap().set_gestures(GestureConfig {
+        double_tap_window: 0.3,
+        long_press: 0.5,
+        repeat_delay: 0.5,
+        repeat_rate: 4.0,
+    });
+
+    (map, actions[0])
+}
+
+//Advances once and returns whether the action repeated, long pressed and double tapped
+fn step(map: &mut Map, handle: ActionHandle) -> (bool, bool, bool) {
+    map.advance(STEP);
+
+    let user = map.user(handle.user()).unwrap();
+    (
+        user.repeated(handle).unwrap(),
+        user.long_pressed(handle).unwrap(),
+        user.double_tapped(handle).unwrap(),
+    )
+}
+
+fn held_for(map: &Map, handle: ActionHandle) -> f64 {
+    map.user(handle.user()).unwrap().held_for(handle).unwrap()
+}
+
+#[test]
+fn holding_repeats_after_the_delay_and_long_presses_once() {
+    let (mut map, space) = map();
+
+    set(&mut map, "Space", true);
+    let (mut repeats, mut long_presses) = (Vec::new(), Vec::new());
+    for i in 0..11 {
+        let (repeated, long_pressed, _) = step(&mut map, space);
+        if repeated {
+            repeats.push(i);
+        }
+        if long_pressed {
+            long_presses.push(i);
+        }
+    }
+
+    //The press itself, then every quarter second once half a second has passed
+    assert_eq!(repeats, vec![0, 4, 6, 8, 10]);
+    assert_eq!(long_presses, vec![4]);
+    assert_eq!(held_for(&map, space), 1.25);
+
+    set(&mut map, "Space", false);
+    assert_eq!(step(&mut map, space), (false, false, false));
+    assert_eq!(held_for(&map, space), 0.0);
+}
+
+#[test]
+fn quick_second_presses_double_tap() {
+    let (mut map, space) = map();
+
+    let mut taps = Vec::new();
+    for down in [true, false, true, false, true] {
+        set(&mut map, "Space", down);
+        taps.push(step(&mut map, space).2);
+    }
+
+    //A third quick press starts over instead of double tapping again
+    assert_eq!(taps, vec![false, false, true, false, false]);
+}
+
+#[test]
+fn slow_second_presses_do_not_double_tap() {
+    let (mut map, space) = map();
+
+    set(&mut map, "Space", true);
+    step(&mut map, space);
+    set(&mut map, "Space", false);
+    step(&mut map, space);
+
+    //Past the window by the time the second press lands
+    step(&mut map, space);
+    step(&mut map, space);
+    set(&mut map, "Space", true);
+    assert_eq!(step(&mut map, space), (true, false, false));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "input")]
+
+use stagehand::input::{
+    touch::{Gesture, Region, TouchEvent, TouchPhase},
+    ActionState, ActionType, InputMap,
+};
+
+fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchEvent {
+    TouchEvent { id, phase, x, y }
+}
+
+#[test]
+fn began_lasts_one_update() {
+    let mut map: InputMap<&'static str> = InputMap::new();
+
+    map.process_touch(touch(1, TouchPhase::Began, 10.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    assert_eq!(map.touches()[0].phase, TouchPhase::Began);
+
+    for _ in 0..3 {
+        map.advance(0.016);
+        assert_eq!(map.touches()[0].phase, TouchPhase::Moved);
+    }
+
+    map.process_touch(touch(1, TouchPhase::Ended, 10.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    assert_eq!(map.touches()[0].phase, TouchPhase::Ended);
+
+    map.advance(0.016);
+    assert!(map.touches().is_empty());
+}
+
+#[test]
+fn quick_touches_tap_and_long_ones_swipe() {
+    let mut map: InputMap<&'static str> = InputMap::new();
+
+    map.process_touch(touch(1, TouchPhase::Began, 10.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    map.process_touch(touch(1, TouchPhase::Ended, 12.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    assert_eq!(map.gestures(), &[Gesture::Tap { x: 12.0, y: 10.0 }]);
+
+    map.process_touch(touch(2, TouchPhase::Began, 10.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    map.process_touch(touch(2, TouchPhase::Moved, 110.0, 10.0))
+        .unwrap();
+    map.process_touch(touch(2, TouchPhase::Ended, 110.0, 10.0))
+        .unwrap();
+    map.advance(0.016);
+    assert_eq!(
+        map.gestures(),
+        &[Gesture::Swipe {
+            x: 10.0,
+            y: 10.0,
+            dx: 100.0,
+            dy: 0.0
+        }]
+    );
+}
+
+#[test]
+fn virtual_buttons_drive_their_input() {
+    let mut map = InputMap::new();
+    let user = map.add_user();
+    let fire = map
+        .add_action(
+            user,
+            "Fire".to_string(),
+            vec!["TouchFire"],
+            ActionType::Digital(ActionState::Up),
+        )
+        .unwrap();
+    map.add_virtual_button(Region::new(0.0, 0.0, 50.0, 50.0), "TouchFire");
+
+    map.process_touch(touch(1, TouchPhase::Began, 25.0, 25.0))
+        .unwrap();
+    map.advance(0.016);
+    assert!(map.get_action_by_handle(fire).unwrap().is_down());
+
+    map.process_touch(touch(1, TouchPhase::Ended, 25.0, 25.0))
+        .unwrap();
+    map.advance(0.016);
+    map.advance(0.016);
+    assert!(!map.get_action_by_handle(fire).unwrap().is_down());
+    //Touches on a control never make gestures
+    assert!(map.gestures().is_empty());
+}